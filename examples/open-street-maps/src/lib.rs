@@ -1,28 +1,7 @@
-use std::io::Cursor;
+use snapr::fetchers::http::HttpTileFetcher;
 
-use image::{DynamicImage, ImageFormat, ImageReader};
-use reqwest::blocking::ClientBuilder;
-
-pub fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
-    let address = format!("https://a.tile.osm.org/{zoom}/{x}/{y}.png");
-
-    let client = ClientBuilder::new()
-        .user_agent("snapr / 0.1.0")
-        .build()
-        .map_err(anyhow::Error::from)?;
-
-    let cursor = client
-        .get(address)
-        .send()
-        .and_then(|response| response.error_for_status())
-        .and_then(|response| response.bytes())
-        .map(Cursor::new)
-        .map_err(anyhow::Error::from)?;
-
-    let mut image_reader = ImageReader::new(cursor);
-    image_reader.set_format(ImageFormat::Png);
-
-    let image = image_reader.decode().map_err(anyhow::Error::from)?;
-
-    Ok(image)
+pub fn tile_fetcher() -> HttpTileFetcher {
+    HttpTileFetcher::new("https://{s}.tile.osm.org/{z}/{x}/{y}.png")
+        .with_subdomains(["a", "b", "c"])
+        .with_user_agent("snapr / 0.1.0")
 }
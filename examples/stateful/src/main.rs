@@ -1,11 +1,8 @@
-use std::io::Cursor;
-
-use image::{DynamicImage, ImageFormat, ImageReader};
-use reqwest::blocking::{Client, ClientBuilder};
-use snapr::{fetchers::IndividualTileFetcher, SnaprBuilder, TileFetcher};
+use snapr::{fetchers::http::HttpTileFetcher, SnaprBuilder, TileFetcher};
 
 fn main() -> Result<(), anyhow::Error> {
-    let tile_fetcher = OSMTileFetcher::new()?;
+    // `HttpTileFetcher` holds onto its HTTP client, reusing it for every tile it fetches.
+    let tile_fetcher = HttpTileFetcher::new("https://a.tile.osm.org/{z}/{x}/{y}.png");
 
     let snapr = SnaprBuilder::new()
         .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
@@ -21,40 +18,3 @@ fn main() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
-
-struct OSMTileFetcher(Client);
-
-impl OSMTileFetcher {
-    fn new() -> Result<Self, anyhow::Error> {
-        let user_agent = format!("snapr/{version}", version = env!("CARGO_PKG_VERSION"));
-
-        let client = ClientBuilder::new()
-            .user_agent(user_agent)
-            .build()
-            .map_err(anyhow::Error::from)?;
-
-        Ok(Self(client))
-    }
-}
-
-impl IndividualTileFetcher for OSMTileFetcher {
-    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
-        let address = format!("https://a.tile.osm.org/{zoom}/{x}/{y}.png");
-
-        let cursor = self
-            .0
-            .get(address)
-            .send()
-            .and_then(|response| response.error_for_status())
-            .and_then(|response| response.bytes())
-            .map(Cursor::new)
-            .map_err(anyhow::Error::from)?;
-
-        let mut image_reader = ImageReader::new(cursor);
-        image_reader.set_format(ImageFormat::Png);
-
-        let image = image_reader.decode().map_err(anyhow::Error::from)?;
-
-        Ok(image)
-    }
-}
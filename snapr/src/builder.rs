@@ -1,8 +1,12 @@
-use std::fmt;
+use std::{fmt, sync::Arc};
 
 use macros::impl_snapr_builder;
 
-use crate::{Error, Snapr, TileFetcher, Zoom};
+use crate::{
+    projection::{Projection, WebMercator},
+    AxisOrder, Error, MissingTilePolicy, Padding, Snapr, TileFetcher, TileLayer, TileSizePolicy,
+    Zoom,
+};
 
 pub(crate) mod macros {
     macro_rules! impl_snapr_builder {
@@ -21,6 +25,12 @@ pub(crate) mod macros {
                     }
                 }
 
+                #[doc = concat!("Adds a [`TileLayer`] to the [`", stringify!($snapr_ident), "::tile_layers`] field, composited on top of the previously added layers.")]
+                pub fn with_tile_layer(mut self, tile_layer: TileLayer<$tile_fetcher_ty>) -> Self {
+                    self.tile_layers.push(tile_layer);
+                    self
+                }
+
                 #[doc = concat!("Configures the `tile_size` to be used in the [`", stringify!($snapr_ident), "::tile_size`] field.")]
                 pub fn with_tile_size(self, tile_size: u32) -> Self {
                     Self {
@@ -52,6 +62,54 @@ pub(crate) mod macros {
                         ..self
                     }
                 }
+
+                #[doc = concat!("Configures the `padding` to be used in the [`", stringify!($snapr_ident), "::padding`] field.")]
+                pub fn with_padding<P: Into<Padding>>(self, padding: P) -> Self {
+                    Self {
+                        padding: Some(padding.into()),
+                        ..self
+                    }
+                }
+
+                #[doc = concat!("Configures the `pixel_ratio` to be used in the [`", stringify!($snapr_ident), "::pixel_ratio`] field.")]
+                pub fn with_pixel_ratio(self, pixel_ratio: f32) -> Self {
+                    Self {
+                        pixel_ratio: Some(pixel_ratio),
+                        ..self
+                    }
+                }
+
+                #[doc = concat!("Configures the [`Projection`] to be used in the [`", stringify!($snapr_ident), "::projection`] field.")]
+                pub fn with_projection<P: Projection + 'static>(self, projection: P) -> Self {
+                    Self {
+                        projection: Some(Arc::new(projection)),
+                        ..self
+                    }
+                }
+
+                #[doc = concat!("Configures the [`AxisOrder`] to be used in the [`", stringify!($snapr_ident), "::axis_order`] field.")]
+                pub fn with_axis_order(self, axis_order: AxisOrder) -> Self {
+                    Self {
+                        axis_order: Some(axis_order),
+                        ..self
+                    }
+                }
+
+                #[doc = concat!("Configures the [`MissingTilePolicy`] to be used in the [`", stringify!($snapr_ident), "::missing_tile_policy`] field.")]
+                pub fn with_missing_tile_policy(self, missing_tile_policy: MissingTilePolicy) -> Self {
+                    Self {
+                        missing_tile_policy: Some(missing_tile_policy),
+                        ..self
+                    }
+                }
+
+                #[doc = concat!("Configures the [`TileSizePolicy`] to be used in the [`", stringify!($snapr_ident), "::tile_size_policy`] field.")]
+                pub fn with_tile_size_policy(self, tile_size_policy: TileSizePolicy) -> Self {
+                    Self {
+                        tile_size_policy: Some(tile_size_policy),
+                        ..self
+                    }
+                }
             }
         };
     }
@@ -80,10 +138,17 @@ pub(crate) mod macros {
 #[derive(Default)]
 pub struct SnaprBuilder<'a> {
     tile_fetcher: Option<TileFetcher<'a>>,
+    tile_layers: Vec<TileLayer<TileFetcher<'a>>>,
     tile_size: Option<u32>,
     height: Option<u32>,
     width: Option<u32>,
     zoom: Option<Zoom>,
+    padding: Option<Padding>,
+    pixel_ratio: Option<f32>,
+    projection: Option<Arc<dyn Projection>>,
+    axis_order: Option<AxisOrder>,
+    missing_tile_policy: Option<MissingTilePolicy>,
+    tile_size_policy: Option<TileSizePolicy>,
 }
 
 impl<'a> SnaprBuilder<'a> {
@@ -113,17 +178,44 @@ impl<'a> SnaprBuilder<'a> {
             });
         };
 
+        let tile_layers = self.tile_layers;
         let tile_size = self.tile_size.unwrap_or(256);
         let height = self.height.unwrap_or(600);
         let width = self.width.unwrap_or(800);
         let zoom = self.zoom.unwrap_or_default();
+        let padding = self.padding.unwrap_or_default();
+        let pixel_ratio = self.pixel_ratio.unwrap_or(1.0);
+        let projection = self.projection.unwrap_or_else(|| Arc::new(WebMercator));
+        let axis_order = self.axis_order.unwrap_or_default();
+        let missing_tile_policy = self.missing_tile_policy.unwrap_or_default();
+        let tile_size_policy = self.tile_size_policy.unwrap_or_default();
+
+        if !zoom.is_valid() {
+            return Err(Error::Builder {
+                reason: "field `zoom` needs to be between zero and `Zoom::MAX_LEVEL`".to_string(),
+            });
+        }
+
+        if !(pixel_ratio.is_finite() && pixel_ratio > 0.0) {
+            return Err(Error::Builder {
+                reason: "field `pixel_ratio` needs to be a finite number greater than zero"
+                    .to_string(),
+            });
+        }
 
         let snapr = Snapr {
             tile_fetcher,
+            tile_layers,
             tile_size,
             height,
             width,
             zoom,
+            padding,
+            pixel_ratio,
+            projection,
+            axis_order,
+            missing_tile_policy,
+            tile_size_policy,
         };
 
         Ok(snapr)
@@ -139,10 +231,16 @@ impl_snapr_builder!(
 impl<'a> fmt::Debug for SnaprBuilder<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SnaprBuilder")
+            .field("tile_layers", &self.tile_layers)
             .field("tile_size", &self.tile_size)
             .field("height", &self.height)
             .field("width", &self.width)
             .field("zoom", &self.zoom)
+            .field("padding", &self.padding)
+            .field("pixel_ratio", &self.pixel_ratio)
+            .field("axis_order", &self.axis_order)
+            .field("missing_tile_policy", &self.missing_tile_policy)
+            .field("tile_size_policy", &self.tile_size_policy)
             .finish()
     }
 }
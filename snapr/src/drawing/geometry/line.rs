@@ -93,7 +93,7 @@ impl_styled_geo!(
                     ..Paint::default()
                 },
                 &Stroke {
-                    width: border,
+                    width: border * context.pixel_ratio,
                     ..Stroke::default()
                 },
                 Transform::default(),
@@ -109,7 +109,7 @@ impl_styled_geo!(
                 ..Paint::default()
             },
             &Stroke {
-                width: style.width,
+                width: style.width * context.pixel_ratio,
                 ..Stroke::default()
             },
             Transform::default(),
@@ -185,7 +185,7 @@ impl_styled_geo!(
                         ..Paint::default()
                     },
                     &Stroke {
-                        width: border,
+                        width: border * context.pixel_ratio,
                         ..Stroke::default()
                     },
                     Transform::default(),
@@ -201,7 +201,7 @@ impl_styled_geo!(
                     ..Paint::default()
                 },
                 &Stroke {
-                    width: style.width,
+                    width: style.width * context.pixel_ratio,
                     ..Stroke::default()
                 },
                 Transform::default(),
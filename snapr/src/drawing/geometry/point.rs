@@ -19,6 +19,15 @@ pub enum Shape {
 }
 
 impl Shape {
+    /// Returns a copy of the [`Shape`] with its dimensions multiplied by the given `factor`.
+    pub fn scaled(&self, factor: f32) -> Self {
+        match self {
+            Self::Circle { radius } => Self::Circle {
+                radius: radius * factor,
+            },
+        }
+    }
+
     /// Converts the [`Shape`] to a [`Path`] modeling the selected variant.
     pub fn to_path(&self, x: f32, y: f32) -> Result<Path, crate::Error> {
         let mut path_builder = PathBuilder::new();
@@ -98,7 +107,7 @@ impl_styled_geo!(
 
             #[cfg(feature = "svg")]
             Representation::Svg(svg) => {
-                let svg = svg.try_as_svg((point.x(), point.y()))?;
+                let svg = svg.try_as_svg((point.x(), point.y()), context.pixel_ratio)?;
                 svg.draw(pixmap, context)?;
 
                 return Ok(());
@@ -110,7 +119,9 @@ impl_styled_geo!(
             tracing::trace!(position = ?point, "rendering `Point` to `pixmap`");
         }
 
-        let shape = shape.to_path(point.x() as f32, point.y() as f32)?;
+        let shape = shape
+            .scaled(context.pixel_ratio)
+            .to_path(point.x() as f32, point.y() as f32)?;
 
         pixmap.fill_path(
             &shape,
@@ -133,7 +144,7 @@ impl_styled_geo!(
                     ..Paint::default()
                 },
                 &Stroke {
-                    width: border,
+                    width: border * context.pixel_ratio,
                     ..Stroke::default()
                 },
                 Transform::default(),
@@ -143,7 +154,7 @@ impl_styled_geo!(
 
         #[cfg(feature = "svg")]
         if let Some(label) = &style.label {
-            let svg = label.try_as_svg((point.x(), point.y()))?;
+            let svg = label.try_as_svg((point.x(), point.y()), context.pixel_ratio)?;
             svg.draw(pixmap, context)?;
         }
 
@@ -103,7 +103,7 @@ impl_styled_geo!(
                         ..Paint::default()
                     },
                     &Stroke {
-                        width: border,
+                        width: border * context.pixel_ratio,
                         ..Stroke::default()
                     },
                     Transform::default(),
@@ -119,7 +119,7 @@ impl_styled_geo!(
                     ..Paint::default()
                 },
                 &Stroke {
-                    width: style.line_style.width,
+                    width: style.line_style.width * context.pixel_ratio,
                     ..Stroke::default()
                 },
                 Transform::default(),
@@ -2,7 +2,7 @@
 
 use tiny_skia::Pixmap;
 
-use crate::Snapr;
+use crate::{snapshot::PixelMapping, Snapr};
 
 pub mod geometry;
 pub mod style;
@@ -15,7 +15,20 @@ pub mod svg;
 pub struct Context<'a> {
     pub snapr: &'a Snapr<'a>,
     pub center: geo::Point<f64>,
-    pub zoom: u8,
+    pub zoom: f64,
+
+    /// Bearing of the snapshot, see [`Viewport::bearing`](crate::Viewport::bearing).
+    pub bearing: f64,
+
+    /// Ratio between physical and logical pixels, see [`SnaprBuilder::with_pixel_ratio`](crate::SnaprBuilder::with_pixel_ratio).
+    /// Style dimensions of [`Drawables`](Drawable) are expressed in logical pixels and should be multiplied by this ratio when drawn.
+    pub pixel_ratio: f32,
+
+    /// Width of the snapshot in logical pixels, see [`SnapshotOptions::width`](crate::SnapshotOptions::width).
+    pub width: u32,
+
+    /// Height of the snapshot in logical pixels, see [`SnapshotOptions::height`](crate::SnapshotOptions::height).
+    pub height: u32,
 
     /// Relative index of the current [`Drawable`] being _drawn_.
     pub index: usize,
@@ -24,14 +37,35 @@ pub struct Context<'a> {
 impl<'a> Context<'a> {
     /// Converts an [`EPSG:4326`](https://epsg.io/4326) coordinate to one that represents a pixel in a snapshot.
     /// Used as a shortcut in converting coordinates during drawing.
+    ///
+    /// The returned pixel is in physical pixels, meaning it already accounts for the [`bearing`](Self::bearing) and [`pixel_ratio`](Self::pixel_ratio).
     pub fn epsg_4326_to_pixel(&self, coord: &geo::Coord<f64>) -> geo::Coord<i32> {
-        let epsg_3857_point = Snapr::epsg_4326_to_epsg_3857(self.zoom, geo::Point::from(*coord))
-            - Snapr::epsg_4326_to_epsg_3857(self.zoom, self.center);
+        let pixel = self.mapping().coordinate_to_pixel(*coord);
+
+        geo::coord!(x: pixel.x.round() as i32, y: pixel.y.round() as i32)
+    }
+
+    /// Inverse of [`epsg_4326_to_pixel`](Self::epsg_4326_to_pixel), converts a physical pixel in a snapshot to an [`EPSG:4326`](https://epsg.io/4326) coordinate.
+    /// Longitudes of the returned coordinate are wrapped to be within `-180` and `180` degrees.
+    ///
+    /// See [`Snapshot::pixel_to_coordinate`](crate::Snapshot::pixel_to_coordinate) for converting pixels once a snapshot has been generated.
+    pub fn pixel_to_epsg_4326(&self, pixel: &geo::Coord<f64>) -> geo::Coord<f64> {
+        self.mapping().pixel_to_coordinate(*pixel)
+    }
 
-        geo::coord!(
-            x: (epsg_3857_point.x().fract() * self.snapr.tile_size as f64 + self.snapr.width as f64 / 2.0).round() as i32,
-            y: (epsg_3857_point.y().fract() * self.snapr.tile_size as f64 + self.snapr.height as f64 / 2.0).round() as i32,
-        )
+    /// Returns the [`PixelMapping`] between coordinates and the pixels of the snapshot being drawn.
+    fn mapping(&self) -> PixelMapping<'_> {
+        PixelMapping {
+            projection: &*self.snapr.projection,
+            axis_order: self.snapr.axis_order,
+            center: self.center,
+            zoom: self.zoom,
+            bearing: self.bearing,
+            width: self.width,
+            height: self.height,
+            pixel_ratio: self.pixel_ratio,
+            tile_size: self.snapr.tile_size,
+        }
     }
 }
 
@@ -1,5 +1,7 @@
 //! Contains utilities to make rendering SVGs breezy-er.
 
+use std::sync::OnceLock;
+
 use resvg::{
     render,
     usvg::{Options, Tree},
@@ -17,13 +19,18 @@ pub struct Svg {
 
 impl Svg {
     /// Attempts to convert the [`SvgOptions`] into a valid [`Svg`].
-    pub(crate) fn try_as_svg(&self, pixel: (i32, i32)) -> Result<SpatialSvg, crate::Error> {
-        let mut options = Options::default();
-        options.fontdb_mut().load_system_fonts();
+    /// The `offset` and size of the SVG are multiplied by the given `pixel_ratio`.
+    pub(crate) fn try_as_svg(
+        &self,
+        pixel: (i32, i32),
+        pixel_ratio: f32,
+    ) -> Result<SpatialSvg, crate::Error> {
+        let offset = scale_offset(self.offset, pixel_ratio);
 
         let svg = SpatialSvg {
-            pixel: (pixel.0 - self.offset.0, pixel.1 - self.offset.1),
-            tree: Tree::from_str(&self.svg, &options)?,
+            pixel: (pixel.0 - offset.0, pixel.1 - offset.1),
+            scale: pixel_ratio,
+            tree: Tree::from_str(&self.svg, options())?,
         };
 
         Ok(svg)
@@ -54,7 +61,12 @@ impl Default for Label {
 
 impl Label {
     /// Attempts to convert the [`LabelStyle`] into a valid [`Svg`].
-    pub(crate) fn try_as_svg(&self, pixel: (i32, i32)) -> Result<SpatialSvg, crate::Error> {
+    /// The `font_size`, `offset`, and border of the label are multiplied by the given `pixel_ratio`.
+    pub(crate) fn try_as_svg(
+        &self,
+        pixel: (i32, i32),
+        pixel_ratio: f32,
+    ) -> Result<SpatialSvg, crate::Error> {
         let raw_svg = format!(
             r##"
             <svg xmlns="http://www.w3.org/2000/svg">
@@ -63,28 +75,48 @@ impl Label {
             "##,
             foreground = self.color_options.foreground_as_hex_code(),
             font_family = self.font_family,
-            font_size = self.font_size,
+            font_size = self.font_size * pixel_ratio,
             background = self.color_options.background_as_hex_code(),
-            border = self.color_options.border.unwrap_or(0.0),
+            border = self.color_options.border.unwrap_or(0.0) * pixel_ratio,
             text = self.text,
         );
 
-        let mut options = Options::default();
-        options.fontdb_mut().load_system_fonts();
+        let offset = scale_offset(self.offset, pixel_ratio);
 
         let svg = SpatialSvg {
-            pixel: (pixel.0 - self.offset.0, pixel.1 - self.offset.1),
-            tree: Tree::from_str(&raw_svg, &options)?,
+            pixel: (pixel.0 - offset.0, pixel.1 - offset.1),
+            scale: 1.0,
+            tree: Tree::from_str(&raw_svg, options())?,
         };
 
         Ok(svg)
     }
 }
 
-/// Represents an SVG that's drawn centered on a certain [`pixel`](Self::pixel).
+/// Returns the [`Options`] used to parse every SVG, loading the fonts of the system only once, when first called.
+fn options() -> &'static Options<'static> {
+    static OPTIONS: OnceLock<Options<'static>> = OnceLock::new();
+
+    OPTIONS.get_or_init(|| {
+        let mut options = Options::default();
+        options.fontdb_mut().load_system_fonts();
+        options
+    })
+}
+
+/// Multiplies an `offset` in logical pixels by the given `pixel_ratio`.
+fn scale_offset(offset: (i32, i32), pixel_ratio: f32) -> (i32, i32) {
+    (
+        (offset.0 as f32 * pixel_ratio).round() as i32,
+        (offset.1 as f32 * pixel_ratio).round() as i32,
+    )
+}
+
+/// Represents an SVG that's drawn centered on a certain [`pixel`](Self::pixel), scaled by a certain [`scale`](Self::scale).
 #[derive(Clone, Debug)]
 pub(crate) struct SpatialSvg {
     pub(crate) pixel: (i32, i32),
+    pub(crate) scale: f32,
     pub(crate) tree: Tree,
 }
 
@@ -94,9 +126,17 @@ impl Drawable for SpatialSvg {
         tracing::instrument(level = "TRACE", skip(self, pixmap), err)
     )]
     fn draw(&self, pixmap: &mut Pixmap, _: &Context) -> Result<(), crate::Error> {
-        let SpatialSvg { pixel, tree } = self;
+        self.render(pixmap);
+        Ok(())
+    }
+}
+
+impl SpatialSvg {
+    /// Renders the [`SpatialSvg`] to the given `pixmap`, without requiring a [`Context`].
+    pub(crate) fn render(&self, pixmap: &mut Pixmap) {
+        let SpatialSvg { pixel, scale, tree } = self;
 
-        let svg_size = tree.size();
+        let (svg_width, svg_height) = (tree.size().width() * scale, tree.size().height() * scale);
         let (x, y) = *pixel;
 
         #[cfg(feature = "tracing")]
@@ -106,13 +146,9 @@ impl Drawable for SpatialSvg {
 
         render(
             tree,
-            Transform::from_translate(
-                x as f32 - (svg_size.width() / 2.0),
-                y as f32 - (svg_size.height() / 2.0),
-            ),
+            Transform::from_scale(*scale, *scale)
+                .post_translate(x as f32 - (svg_width / 2.0), y as f32 - (svg_height / 2.0)),
             &mut pixmap.as_mut(),
         );
-
-        Ok(())
     }
 }
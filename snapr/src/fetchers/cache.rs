@@ -0,0 +1,273 @@
+//! Contains an in-memory cache of tiles that can wrap any [`TileFetcher`].
+//! See [`TileCache`] for more details.
+
+use std::{
+    fmt,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+};
+
+use image::DynamicImage;
+use lru::LruCache;
+
+use crate::Error;
+
+use super::{BatchTileFetcher, IndividualTileFetcher, TileFetcher};
+
+/// Key of a cached tile, made up of the source it was fetched from, and its `x`, `y`, and `zoom` values.
+type TileKey = (u64, i32, i32, u8);
+
+/// Bounded, thread-safe, in-memory cache of tiles evicting the least recently used tile when full.
+///
+/// Cloning a [`TileCache`] is cheap, and the clones share the same tiles and [`CacheStats`].
+/// This enables a single [`TileCache`] to be shared by multiple [`TileFetchers`](TileFetcher), and inspected after wrapping them.
+/// Each call to [`wrap`](TileCache::wrap) is a separate source, meaning tiles are never served across the [`TileFetchers`](TileFetcher) sharing the [`TileCache`],
+/// even though they share its capacity.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::num::NonZeroUsize;
+///
+/// use image::DynamicImage;
+/// use snapr::{fetchers::cache::TileCache, SnaprBuilder, TileFetcher, Viewport};
+///
+/// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+///     Ok(DynamicImage::new_rgba8(256, 256))
+/// }
+///
+/// let cache = TileCache::new(NonZeroUsize::new(256).unwrap());
+///
+/// let snapr = SnaprBuilder::new()
+///     .with_tile_fetcher(cache.wrap(TileFetcher::individual(tile_fetcher)))
+///     .build()
+///     .unwrap();
+///
+/// let viewport = Viewport::new(geo::point!(x: 40.807997, y: -96.699724), 15.0);
+///
+/// snapr.snapshot_with_viewport(Vec::new(), viewport).unwrap();
+/// snapr.snapshot_with_viewport(Vec::new(), viewport).unwrap();
+///
+/// let stats = cache.stats();
+/// assert_eq!(stats.hits, stats.misses);
+/// ```
+#[derive(Clone)]
+pub struct TileCache {
+    inner: Arc<CacheState>,
+}
+
+struct CacheState {
+    tiles: Mutex<LruCache<TileKey, DynamicImage>>,
+
+    /// Source assigned to the next wrapped [`TileFetcher`].
+    next_source: AtomicU64,
+
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Statistics of a [`TileCache`], see [`TileCache::stats`].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Amount of tiles that were served from the cache.
+    pub hits: u64,
+
+    /// Amount of tiles that had to be fetched from the wrapped [`TileFetcher`].
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Returns the ratio of tiles that were served from the cache, or `0.0` when no tiles have been requested.
+    pub fn hit_ratio(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl TileCache {
+    /// Constructs a new [`TileCache`] holding up to `capacity` tiles.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        let inner = CacheState {
+            tiles: Mutex::new(LruCache::new(capacity)),
+            next_source: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        };
+
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Wraps the given `tile_fetcher`, serving tiles from the [`TileCache`] and falling back to the `tile_fetcher` for tiles that aren't cached.
+    /// The returned [`TileFetcher`] is of the same variant as the given `tile_fetcher`.
+    /// Tiles cached for the returned [`TileFetcher`] are only ever served to it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use image::{DynamicImage, Rgba, RgbaImage};
+    /// use snapr::{fetchers::cache::TileCache, TileFetcher};
+    ///
+    /// fn basemap(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     Ok(RgbaImage::from_pixel(256, 256, Rgba([255, 255, 255, 255])).into())
+    /// }
+    ///
+    /// fn overlay(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     Ok(RgbaImage::from_pixel(256, 256, Rgba([255, 0, 0, 128])).into())
+    /// }
+    ///
+    /// let cache = TileCache::new(NonZeroUsize::new(256).unwrap());
+    ///
+    /// let TileFetcher::Individual(basemap) = cache.wrap(TileFetcher::individual(basemap)) else { unreachable!() };
+    /// let TileFetcher::Individual(overlay) = cache.wrap(TileFetcher::individual(overlay)) else { unreachable!() };
+    ///
+    /// basemap.fetch_tile(0, 0, 0).unwrap();
+    /// let tile = overlay.fetch_tile(0, 0, 0).unwrap();
+    ///
+    /// assert_eq!(tile.to_rgba8().get_pixel(0, 0), &Rgba([255, 0, 0, 128]));
+    /// assert_eq!(cache.len(), 2);
+    /// ```
+    pub fn wrap<'a>(&self, tile_fetcher: TileFetcher<'a>) -> TileFetcher<'a> {
+        let source = self.inner.next_source.fetch_add(1, Ordering::Relaxed);
+
+        match tile_fetcher {
+            TileFetcher::Individual(tile_fetcher) => TileFetcher::individual(CachedIndividual {
+                tile_fetcher,
+                cache: self.clone(),
+                source,
+            }),
+
+            TileFetcher::Batch(tile_fetcher) => TileFetcher::batch(CachedBatch {
+                tile_fetcher,
+                cache: self.clone(),
+                source,
+            }),
+        }
+    }
+
+    /// Returns the hits and misses of the [`TileCache`] since it was constructed, or last [`cleared`](Self::clear).
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the amount of tiles currently held by the [`TileCache`].
+    pub fn len(&self) -> usize {
+        self.tiles().len()
+    }
+
+    /// Returns `true` if the [`TileCache`] holds no tiles.
+    pub fn is_empty(&self) -> bool {
+        self.tiles().is_empty()
+    }
+
+    /// Removes every tile from the [`TileCache`], and resets its [`CacheStats`].
+    pub fn clear(&self) {
+        self.tiles().clear();
+        self.inner.hits.store(0, Ordering::Relaxed);
+        self.inner.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// Returns a copy of the cached tile at the given `key`, recording a hit or miss accordingly.
+    fn get(&self, key: TileKey) -> Option<DynamicImage> {
+        let tile = self.tiles().get(&key).cloned();
+
+        let counter = match tile {
+            Some(_) => &self.inner.hits,
+            None => &self.inner.misses,
+        };
+
+        counter.fetch_add(1, Ordering::Relaxed);
+        tile
+    }
+
+    fn insert(&self, key: TileKey, tile: DynamicImage) {
+        self.tiles().put(key, tile);
+    }
+
+    fn tiles(&self) -> MutexGuard<'_, LruCache<TileKey, DynamicImage>> {
+        // Tiles are only ever inserted whole, so a panic while holding the lock can't leave them in an inconsistent state.
+        self.inner
+            .tiles
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl fmt::Debug for TileCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tiles = self.tiles();
+
+        f.debug_struct("TileCache")
+            .field("len", &tiles.len())
+            .field("capacity", &tiles.cap())
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+/// [`IndividualTileFetcher`] returned by [`TileCache::wrap`].
+struct CachedIndividual<'a> {
+    tile_fetcher: Box<dyn IndividualTileFetcher + 'a>,
+    cache: TileCache,
+    source: u64,
+}
+
+impl<'a> IndividualTileFetcher for CachedIndividual<'a> {
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        if let Some(tile) = self.cache.get((self.source, x, y, zoom)) {
+            return Ok(tile);
+        }
+
+        let tile = self.tile_fetcher.fetch_tile(x, y, zoom)?;
+        self.cache.insert((self.source, x, y, zoom), tile.clone());
+
+        Ok(tile)
+    }
+}
+
+/// [`BatchTileFetcher`] returned by [`TileCache::wrap`].
+struct CachedBatch<'a> {
+    tile_fetcher: Box<dyn BatchTileFetcher + 'a>,
+    cache: TileCache,
+    source: u64,
+}
+
+impl<'a> BatchTileFetcher for CachedBatch<'a> {
+    fn fetch_tiles(
+        &self,
+        coordinate_matrix: &[(i32, i32)],
+        zoom: u8,
+    ) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+        let mut tiles = Vec::with_capacity(coordinate_matrix.len());
+        let mut missing = Vec::new();
+
+        for &(x, y) in coordinate_matrix {
+            match self.cache.get((self.source, x, y, zoom)) {
+                Some(tile) => tiles.push((x, y, tile)),
+                None => missing.push((x, y)),
+            }
+        }
+
+        if missing.is_empty() {
+            return Ok(tiles);
+        }
+
+        for (x, y, tile) in self.tile_fetcher.fetch_tiles(&missing, zoom)? {
+            self.cache.insert((self.source, x, y, zoom), tile.clone());
+            tiles.push((x, y, tile));
+        }
+
+        Ok(tiles)
+    }
+}
@@ -0,0 +1,69 @@
+//! Contains a tile fetcher reading pre-rendered tiles from a local directory.
+//! See [`DirectoryTileFetcher`] for more details.
+
+use std::{io::ErrorKind, path::PathBuf};
+
+use image::{DynamicImage, ImageReader};
+
+use crate::Error;
+
+use super::{IndividualTileFetcher, TileCoord};
+
+/// Tile fetcher reading pre-rendered tiles from a local directory, located through a path template such as `/tiles/{z}/{x}/{y}.png`.
+///
+/// Placeholders of the template are filled with [`TileCoord::fill_template`].
+/// The format of each tile is sniffed from its contents rather than its extension, and tiles missing from the directory are reported as [`Error::TileNotFound`].
+///
+/// ## Example
+///
+/// ```rust
+/// use image::DynamicImage;
+/// use snapr::{fetchers::{directory::DirectoryTileFetcher, IndividualTileFetcher}, Error};
+///
+/// let root = std::env::temp_dir().join(format!("snapr-directory-example-{}", std::process::id()));
+/// std::fs::create_dir_all(root.join("1/0")).unwrap();
+///
+/// DynamicImage::new_rgba8(256, 256).save(root.join("1/0/1.png")).unwrap();
+///
+/// let template = format!("{root}/{{z}}/{{x}}/{{y}}.png", root = root.display());
+/// let tile_fetcher = DirectoryTileFetcher::new(template);
+///
+/// assert!(tile_fetcher.fetch_tile(0, 1, 1).is_ok());
+/// assert!(matches!(tile_fetcher.fetch_tile(1, 1, 1), Err(Error::TileNotFound { x: 1, y: 1, zoom: 1 })));
+///
+/// std::fs::remove_dir_all(root).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirectoryTileFetcher {
+    template: String,
+}
+
+impl DirectoryTileFetcher {
+    /// Constructs a new [`DirectoryTileFetcher`] reading tiles from paths following the given `template`.
+    pub fn new<T: Into<String>>(template: T) -> Self {
+        Self {
+            template: template.into(),
+        }
+    }
+
+    /// Attempts to return the path the tile at the given `x`, `y`, and `zoom` values is read from.
+    pub fn tile_path(&self, x: i32, y: i32, zoom: u8) -> Result<PathBuf, Error> {
+        let path = TileCoord::new(x, y, zoom).fill_template(&self.template)?;
+        Ok(PathBuf::from(path))
+    }
+}
+
+impl IndividualTileFetcher for DirectoryTileFetcher {
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        let reader = match ImageReader::open(self.tile_path(x, y, zoom)?) {
+            Ok(reader) => reader,
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                return Err(Error::TileNotFound { x, y, zoom })
+            }
+
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(reader.with_guessed_format()?.decode()?)
+    }
+}
@@ -0,0 +1,257 @@
+//! Contains a persistent on-disk cache of tiles that can wrap any [`TileFetcher`].
+//! See [`DiskTileCache`] for more details.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use image::{DynamicImage, ImageFormat};
+
+use crate::Error;
+
+use super::{BatchTileFetcher, IndividualTileFetcher, TileFetcher};
+
+/// Persistent cache of tiles, stored as PNG images under a `{z}/{x}/{y}.png` layout within a root directory.
+///
+/// Tiles older than the configured [`max_age`](Self::with_max_age) are considered expired, and are fetched again from the wrapped [`TileFetcher`].
+/// Failing to write a tile to the cache does not fail the fetch it came from.
+///
+/// Tile fetchers hand over decoded images rather than the bytes they were served as, so every tile is re-encoded as a lossless PNG regardless of its source format.
+/// This costs an encode on every write, and stores tiles served as JPEG or WebP larger than they were served, in exchange for never degrading them further.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use image::DynamicImage;
+/// use snapr::{fetchers::disk_cache::DiskTileCache, TileFetcher};
+///
+/// static FETCHES: AtomicUsize = AtomicUsize::new(0);
+///
+/// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+///     FETCHES.fetch_add(1, Ordering::Relaxed);
+///     Ok(DynamicImage::new_rgba8(256, 256))
+/// }
+///
+/// let root = std::env::temp_dir().join(format!("snapr-disk-cache-{}", std::process::id()));
+/// let cache = DiskTileCache::new(&root);
+///
+/// let TileFetcher::Individual(tile_fetcher) = cache.wrap(TileFetcher::individual(tile_fetcher)) else { unreachable!() };
+///
+/// tile_fetcher.fetch_tile(1, 2, 3).unwrap();
+/// tile_fetcher.fetch_tile(1, 2, 3).unwrap();
+///
+/// // The second fetch is served from the tile written to disk by the first one.
+/// assert_eq!(FETCHES.load(Ordering::Relaxed), 1);
+/// assert!(cache.tile_path(1, 2, 3).exists());
+///
+/// std::fs::remove_dir_all(root).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct DiskTileCache {
+    root: PathBuf,
+    max_age: Option<Duration>,
+}
+
+impl DiskTileCache {
+    /// Constructs a new [`DiskTileCache`] storing tiles under the given `root` directory, which is created on demand.
+    /// Tiles never expire unless a [`max_age`](Self::with_max_age) is configured.
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self {
+            root: root.into(),
+            max_age: None,
+        }
+    }
+
+    /// Configures the age after which cached tiles are considered expired, and fetched again.
+    /// The age of a tile is measured from the last time it was written to the cache.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::{
+    ///     fs::File,
+    ///     sync::atomic::{AtomicUsize, Ordering},
+    ///     time::{Duration, SystemTime},
+    /// };
+    ///
+    /// use image::DynamicImage;
+    /// use snapr::{fetchers::disk_cache::DiskTileCache, TileFetcher};
+    ///
+    /// static FETCHES: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     FETCHES.fetch_add(1, Ordering::Relaxed);
+    ///     Ok(DynamicImage::new_rgba8(256, 256))
+    /// }
+    ///
+    /// let root = std::env::temp_dir().join(format!("snapr-disk-cache-expiry-{}", std::process::id()));
+    /// let cache = DiskTileCache::new(&root).with_max_age(Duration::from_secs(60 * 60));
+    ///
+    /// let TileFetcher::Individual(tile_fetcher) = cache.wrap(TileFetcher::individual(tile_fetcher)) else { unreachable!() };
+    ///
+    /// tile_fetcher.fetch_tile(1, 2, 3).unwrap();
+    /// tile_fetcher.fetch_tile(1, 2, 3).unwrap();
+    ///
+    /// assert_eq!(FETCHES.load(Ordering::Relaxed), 1);
+    ///
+    /// // Ages the cached tile past the `max_age`, so the next fetch reaches the wrapped tile fetcher again.
+    /// let two_hours_ago = SystemTime::now() - Duration::from_secs(2 * 60 * 60);
+    /// File::options().write(true).open(cache.tile_path(1, 2, 3)).unwrap().set_modified(two_hours_ago).unwrap();
+    ///
+    /// tile_fetcher.fetch_tile(1, 2, 3).unwrap();
+    ///
+    /// assert_eq!(FETCHES.load(Ordering::Relaxed), 2);
+    ///
+    /// std::fs::remove_dir_all(root).unwrap();
+    /// ```
+    pub fn with_max_age(self, max_age: Duration) -> Self {
+        Self {
+            max_age: Some(max_age),
+            ..self
+        }
+    }
+
+    /// Returns the root directory of the [`DiskTileCache`].
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the path a tile at the given `x`, `y`, and `zoom` values is stored at.
+    pub fn tile_path(&self, x: i32, y: i32, zoom: u8) -> PathBuf {
+        self.root
+            .join(zoom.to_string())
+            .join(x.to_string())
+            .join(format!("{y}.png"))
+    }
+
+    /// Wraps the given `tile_fetcher`, serving tiles from the [`DiskTileCache`] and falling back to the `tile_fetcher` for tiles that are missing or expired.
+    /// The returned [`TileFetcher`] is of the same variant as the given `tile_fetcher`.
+    pub fn wrap<'a>(&self, tile_fetcher: TileFetcher<'a>) -> TileFetcher<'a> {
+        match tile_fetcher {
+            TileFetcher::Individual(tile_fetcher) => TileFetcher::individual(CachedIndividual {
+                tile_fetcher,
+                cache: self.clone(),
+            }),
+
+            TileFetcher::Batch(tile_fetcher) => TileFetcher::batch(CachedBatch {
+                tile_fetcher,
+                cache: self.clone(),
+            }),
+        }
+    }
+
+    /// Reads the tile at the given `x`, `y`, and `zoom` values, returning [`None`] if it's missing, expired, or can't be decoded.
+    fn read(&self, x: i32, y: i32, zoom: u8) -> Option<DynamicImage> {
+        let path = self.tile_path(x, y, zoom);
+
+        if let Some(max_age) = self.max_age {
+            let modified = fs::metadata(&path).and_then(|metadata| metadata.modified());
+            let age = modified.ok()?.elapsed().unwrap_or_default();
+
+            if age > max_age {
+                return None;
+            }
+        }
+
+        image::open(path).ok()
+    }
+
+    /// Writes the `tile` at the given `x`, `y`, and `zoom` values, replacing any existing tile.
+    fn write(&self, x: i32, y: i32, zoom: u8, tile: &DynamicImage) -> Result<(), Error> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let path = self.tile_path(x, y, zoom);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Tiles are written to a unique temporary file first, so concurrent readers never observe a partially written tile.
+        let temporary_path = path.with_extension(format!(
+            "{id}.{count}.tmp",
+            id = process::id(),
+            count = COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let written = tile
+            .save_with_format(&temporary_path, ImageFormat::Png)
+            .map_err(Error::from)
+            .and_then(|_| fs::rename(&temporary_path, &path).map_err(Error::from));
+
+        if written.is_err() {
+            let _ = fs::remove_file(&temporary_path);
+        }
+
+        written
+    }
+
+    /// Writes the `tile` like [`write`](Self::write), only reporting failures as `tracing` events.
+    fn write_or_skip(&self, x: i32, y: i32, zoom: u8, tile: &DynamicImage) {
+        if let Err(_error) = self.write(x, y, zoom, tile) {
+            #[cfg(feature = "tracing")]
+            {
+                tracing::warn!(x, y, zoom, error = %_error, "failed to write tile to `DiskTileCache`");
+            }
+        }
+    }
+}
+
+/// [`IndividualTileFetcher`] returned by [`DiskTileCache::wrap`].
+struct CachedIndividual<'a> {
+    tile_fetcher: Box<dyn IndividualTileFetcher + 'a>,
+    cache: DiskTileCache,
+}
+
+impl<'a> IndividualTileFetcher for CachedIndividual<'a> {
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        if let Some(tile) = self.cache.read(x, y, zoom) {
+            return Ok(tile);
+        }
+
+        let tile = self.tile_fetcher.fetch_tile(x, y, zoom)?;
+        self.cache.write_or_skip(x, y, zoom, &tile);
+
+        Ok(tile)
+    }
+}
+
+/// [`BatchTileFetcher`] returned by [`DiskTileCache::wrap`].
+struct CachedBatch<'a> {
+    tile_fetcher: Box<dyn BatchTileFetcher + 'a>,
+    cache: DiskTileCache,
+}
+
+impl<'a> BatchTileFetcher for CachedBatch<'a> {
+    fn fetch_tiles(
+        &self,
+        coordinate_matrix: &[(i32, i32)],
+        zoom: u8,
+    ) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+        let mut tiles = Vec::with_capacity(coordinate_matrix.len());
+        let mut missing = Vec::new();
+
+        for &(x, y) in coordinate_matrix {
+            match self.cache.read(x, y, zoom) {
+                Some(tile) => tiles.push((x, y, tile)),
+                None => missing.push((x, y)),
+            }
+        }
+
+        if missing.is_empty() {
+            return Ok(tiles);
+        }
+
+        for (x, y, tile) in self.tile_fetcher.fetch_tiles(&missing, zoom)? {
+            self.cache.write_or_skip(x, y, zoom, &tile);
+            tiles.push((x, y, tile));
+        }
+
+        Ok(tiles)
+    }
+}
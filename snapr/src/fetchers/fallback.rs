@@ -0,0 +1,368 @@
+//! Contains a chain of tile fetchers, falling back to the next one when a fetch fails.
+//! See [`Fallback`] for more details.
+
+use std::collections::HashSet;
+
+use image::DynamicImage;
+
+use crate::Error;
+
+use super::{BatchTileFetcher, IndividualTileFetcher, TileFetcher};
+
+#[cfg(feature = "tokio")]
+use std::sync::Arc;
+
+#[cfg(feature = "tokio")]
+use super::{AsyncBatchTileFetcher, AsyncIndividualTileFetcher, AsyncTileFetcher};
+
+/// Chain of [`TileFetchers`](TileFetcher), fetching tiles from the first one and falling back to the following ones for tiles it failed to fetch.
+///
+/// A chain made only of [`Individual`](TileFetcher::Individual) tile fetchers builds an [`Individual`](TileFetcher::Individual) tile fetcher,
+/// which returns the error of the last tile fetcher when all of them failed.
+///
+/// Otherwise, the chain builds a [`Batch`](TileFetcher::Batch) tile fetcher, requesting each tile fetcher the tiles still missing after the previous ones.
+/// Tiles left out of a batch without an error are also requested from the following tile fetchers.
+/// Tiles that every tile fetcher failed to fetch are left out of the returned tiles, so they're handled like any other missing tile.
+/// An error is only returned if none of the tiles could be fetched, being the error of the last tile fetcher that failed.
+///
+/// ## Example
+///
+/// ```rust
+/// use image::DynamicImage;
+/// use snapr::{fetchers::{fallback::Fallback, IndividualTileFetcher}, Error, TileFetcher};
+///
+/// fn primary(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+///     Err(Error::TileNotFound { x, y, zoom })
+/// }
+///
+/// fn secondary(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+///     Ok(DynamicImage::new_rgba8(256, 256))
+/// }
+///
+/// let tile_fetcher = Fallback::new(TileFetcher::individual(primary))
+///     .or(TileFetcher::individual(secondary))
+///     .into_tile_fetcher();
+///
+/// let TileFetcher::Individual(tile_fetcher) = tile_fetcher else {
+///     unreachable!()
+/// };
+///
+/// assert!(tile_fetcher.fetch_tile(0, 0, 0).is_ok());
+/// ```
+pub struct Fallback<'a> {
+    tile_fetchers: Vec<TileFetcher<'a>>,
+}
+
+impl<'a> Fallback<'a> {
+    /// Constructs a new [`Fallback`] chain, starting with the given `primary` tile fetcher.
+    pub fn new(primary: TileFetcher<'a>) -> Self {
+        Self {
+            tile_fetchers: vec![primary],
+        }
+    }
+
+    /// Appends the given `tile_fetcher` to the end of the chain.
+    pub fn or(mut self, tile_fetcher: TileFetcher<'a>) -> Self {
+        self.tile_fetchers.push(tile_fetcher);
+        self
+    }
+
+    /// Builds a [`TileFetcher`] fetching tiles through the chain.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use image::DynamicImage;
+    /// use snapr::{fetchers::{fallback::Fallback, BatchTileFetcher}, Error, TileFetcher};
+    ///
+    /// fn primary(coordinate_matrix: &[(i32, i32)], zoom: u8) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+    ///     Ok(vec![(0, 0, DynamicImage::new_rgba8(256, 256))])
+    /// }
+    ///
+    /// fn secondary(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+    ///     Err(Error::TileNotFound { x, y, zoom })
+    /// }
+    ///
+    /// let tile_fetcher = Fallback::new(TileFetcher::batch(primary))
+    ///     .or(TileFetcher::individual(secondary))
+    ///     .into_tile_fetcher();
+    ///
+    /// let TileFetcher::Batch(tile_fetcher) = tile_fetcher else {
+    ///     unreachable!()
+    /// };
+    ///
+    /// // Tiles fetched by the `primary` tile fetcher are kept, even though the `secondary` tile fetcher failed.
+    /// let tiles = tile_fetcher.fetch_tiles(&[(0, 0), (1, 0)], 1).unwrap();
+    /// assert_eq!(tiles.iter().map(|&(x, y, _)| (x, y)).collect::<Vec<_>>(), [(0, 0)]);
+    /// ```
+    pub fn into_tile_fetcher(self) -> TileFetcher<'a> {
+        let all_individual = self
+            .tile_fetchers
+            .iter()
+            .all(|tile_fetcher| matches!(tile_fetcher, TileFetcher::Individual(_)));
+
+        if !all_individual {
+            return TileFetcher::batch(FallbackBatch {
+                tile_fetchers: self.tile_fetchers,
+            });
+        }
+
+        let tile_fetchers = self
+            .tile_fetchers
+            .into_iter()
+            .filter_map(|tile_fetcher| match tile_fetcher {
+                TileFetcher::Individual(tile_fetcher) => Some(tile_fetcher),
+                TileFetcher::Batch(_) => None,
+            })
+            .collect();
+
+        TileFetcher::individual(FallbackIndividual { tile_fetchers })
+    }
+}
+
+/// [`IndividualTileFetcher`] returned by [`Fallback::into_tile_fetcher`].
+struct FallbackIndividual<'a> {
+    tile_fetchers: Vec<Box<dyn IndividualTileFetcher + 'a>>,
+}
+
+impl<'a> IndividualTileFetcher for FallbackIndividual<'a> {
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        let mut last_error = Error::TileNotFound { x, y, zoom };
+
+        for tile_fetcher in &self.tile_fetchers {
+            match tile_fetcher.fetch_tile(x, y, zoom) {
+                Ok(tile) => return Ok(tile),
+                Err(error) => last_error = error,
+            }
+        }
+
+        Err(last_error)
+    }
+}
+
+/// [`BatchTileFetcher`] returned by [`Fallback::into_tile_fetcher`].
+struct FallbackBatch<'a> {
+    tile_fetchers: Vec<TileFetcher<'a>>,
+}
+
+impl<'a> BatchTileFetcher for FallbackBatch<'a> {
+    fn fetch_tiles(
+        &self,
+        coordinate_matrix: &[(i32, i32)],
+        zoom: u8,
+    ) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+        let mut tiles = Vec::with_capacity(coordinate_matrix.len());
+        let mut missing = coordinate_matrix.to_vec();
+        let mut last_error = None;
+
+        for tile_fetcher in &self.tile_fetchers {
+            if missing.is_empty() {
+                break;
+            }
+
+            last_error = None;
+
+            match tile_fetcher {
+                TileFetcher::Individual(tile_fetcher) => {
+                    missing.retain(|&(x, y)| match tile_fetcher.fetch_tile(x, y, zoom) {
+                        Ok(tile) => {
+                            tiles.push((x, y, tile));
+                            false
+                        }
+
+                        Err(error) => {
+                            last_error = Some(error);
+                            true
+                        }
+                    });
+                }
+
+                TileFetcher::Batch(tile_fetcher) => {
+                    match tile_fetcher.fetch_tiles(&missing, zoom) {
+                        Ok(fetched) => retain_missing(&mut missing, &mut tiles, fetched),
+                        Err(error) => last_error = Some(error),
+                    }
+                }
+            }
+        }
+
+        match last_error {
+            Some(error) if tiles.is_empty() => Err(error),
+            _ => Ok(tiles),
+        }
+    }
+}
+
+/// Moves the `fetched` tiles into `tiles`, removing their positions from `missing`.
+fn retain_missing(
+    missing: &mut Vec<(i32, i32)>,
+    tiles: &mut Vec<(i32, i32, DynamicImage)>,
+    fetched: Vec<(i32, i32, DynamicImage)>,
+) {
+    let fetched_positions = fetched
+        .iter()
+        .map(|&(x, y, _)| (x, y))
+        .collect::<HashSet<_>>();
+
+    missing.retain(|position| !fetched_positions.contains(position));
+    tiles.extend(fetched);
+}
+
+/// Chain of [`AsyncTileFetchers`](AsyncTileFetcher), behaving like [`Fallback`] for asynchronous tile fetchers.
+///
+/// ## Example
+///
+/// ```rust
+/// use image::DynamicImage;
+/// use snapr::{fetchers::fallback::AsyncFallback, AsyncTileFetcher, Error};
+///
+/// async fn primary(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+///     Err(Error::TileNotFound { x, y, zoom })
+/// }
+///
+/// async fn secondary(coordinate_matrix: Vec<(i32, i32)>, zoom: u8) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+///     Ok(coordinate_matrix.into_iter().map(|(x, y)| (x, y, DynamicImage::new_rgba8(256, 256))).collect())
+/// }
+///
+/// let tile_fetcher = AsyncFallback::new(AsyncTileFetcher::individual(primary))
+///     .or(AsyncTileFetcher::batch(secondary))
+///     .into_tile_fetcher();
+///
+/// assert!(matches!(tile_fetcher, AsyncTileFetcher::Batch(_)));
+/// ```
+#[cfg(feature = "tokio")]
+pub struct AsyncFallback<'a> {
+    tile_fetchers: Vec<AsyncTileFetcher<'a>>,
+}
+
+#[cfg(feature = "tokio")]
+impl<'a> AsyncFallback<'a> {
+    /// Constructs a new [`AsyncFallback`] chain, starting with the given `primary` tile fetcher.
+    pub fn new(primary: AsyncTileFetcher<'a>) -> Self {
+        Self {
+            tile_fetchers: vec![primary],
+        }
+    }
+
+    /// Appends the given `tile_fetcher` to the end of the chain.
+    pub fn or(mut self, tile_fetcher: AsyncTileFetcher<'a>) -> Self {
+        self.tile_fetchers.push(tile_fetcher);
+        self
+    }
+
+    /// Builds an [`AsyncTileFetcher`] fetching tiles through the chain.
+    pub fn into_tile_fetcher(self) -> AsyncTileFetcher<'a> {
+        let all_individual = self
+            .tile_fetchers
+            .iter()
+            .all(|tile_fetcher| matches!(tile_fetcher, AsyncTileFetcher::Individual(_)));
+
+        if !all_individual {
+            return AsyncTileFetcher::batch(AsyncFallbackBatch {
+                tile_fetchers: self.tile_fetchers,
+            });
+        }
+
+        let tile_fetchers = self
+            .tile_fetchers
+            .into_iter()
+            .filter_map(|tile_fetcher| match tile_fetcher {
+                AsyncTileFetcher::Individual(tile_fetcher) => Some(tile_fetcher),
+                AsyncTileFetcher::Batch(_) => None,
+            })
+            .collect();
+
+        AsyncTileFetcher::individual(AsyncFallbackIndividual { tile_fetchers })
+    }
+}
+
+/// [`AsyncIndividualTileFetcher`] returned by [`AsyncFallback::into_tile_fetcher`].
+#[cfg(feature = "tokio")]
+struct AsyncFallbackIndividual {
+    tile_fetchers: Vec<Arc<dyn AsyncIndividualTileFetcher>>,
+}
+
+#[cfg(feature = "tokio")]
+#[async_trait::async_trait]
+impl AsyncIndividualTileFetcher for AsyncFallbackIndividual {
+    async fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        let mut last_error = Error::TileNotFound { x, y, zoom };
+
+        for tile_fetcher in &self.tile_fetchers {
+            match tile_fetcher.fetch_tile(x, y, zoom).await {
+                Ok(tile) => return Ok(tile),
+                Err(error) => last_error = error,
+            }
+        }
+
+        Err(last_error)
+    }
+}
+
+/// [`AsyncBatchTileFetcher`] returned by [`AsyncFallback::into_tile_fetcher`].
+#[cfg(feature = "tokio")]
+struct AsyncFallbackBatch<'a> {
+    tile_fetchers: Vec<AsyncTileFetcher<'a>>,
+}
+
+#[cfg(feature = "tokio")]
+#[async_trait::async_trait]
+impl<'a> AsyncBatchTileFetcher for AsyncFallbackBatch<'a> {
+    async fn fetch_tiles(
+        &self,
+        coordinate_matrix: Vec<(i32, i32)>,
+        zoom: u8,
+    ) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+        use tokio::task::JoinSet;
+
+        let mut tiles = Vec::with_capacity(coordinate_matrix.len());
+        let mut missing = coordinate_matrix;
+        let mut last_error = None;
+
+        for tile_fetcher in &self.tile_fetchers {
+            if missing.is_empty() {
+                break;
+            }
+
+            last_error = None;
+
+            match tile_fetcher {
+                AsyncTileFetcher::Individual(tile_fetcher) => {
+                    let mut tasks = JoinSet::new();
+
+                    for &(x, y) in &missing {
+                        let tile_fetcher = tile_fetcher.clone();
+
+                        tasks.spawn(async move {
+                            let tile = tile_fetcher.fetch_tile(x, y, zoom).await;
+                            tile.map(|tile| (x, y, tile))
+                        });
+                    }
+
+                    let mut fetched = Vec::with_capacity(missing.len());
+
+                    while let Some(task) = tasks.join_next().await {
+                        match task.map_err(|_| Error::AsynchronousTaskPanic) {
+                            Ok(Ok(tile)) => fetched.push(tile),
+                            Ok(Err(error)) | Err(error) => last_error = Some(error),
+                        }
+                    }
+
+                    retain_missing(&mut missing, &mut tiles, fetched);
+                }
+
+                AsyncTileFetcher::Batch(tile_fetcher) => {
+                    match tile_fetcher.fetch_tiles(missing.clone(), zoom).await {
+                        Ok(fetched) => retain_missing(&mut missing, &mut tiles, fetched),
+                        Err(error) => last_error = Some(error),
+                    }
+                }
+            }
+        }
+
+        match last_error {
+            Some(error) if tiles.is_empty() => Err(error),
+            _ => Ok(tiles),
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! Contains a tile fetcher requesting tiles from HTTP tile servers.
+//! See [`HttpTileFetcher`] for more details.
+
+use std::sync::OnceLock;
+
+use image::DynamicImage;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    StatusCode,
+};
+
+use crate::Error;
+
+use super::{IndividualTileFetcher, TileCoord};
+
+/// Tile fetcher requesting tiles from an HTTP tile server, located through a URL template such as `https://{s}.tile.osm.org/{z}/{x}/{y}{r}.png`.
+///
+/// On top of the placeholders filled by [`TileCoord::fill_template`], the template supports:
+/// - `{s}`, filled with one of the configured [`subdomains`](Self::with_subdomains).
+/// - `{r}`, filled with `@2x` when [`retina`](Self::with_retina) tiles are requested, and left empty otherwise.
+///
+/// Underlying HTTP clients are created once, on first use, and reused for every following request.
+/// Tiles the server responds to with a `404 Not Found` status are reported as [`Error::TileNotFound`].
+///
+/// ## Example
+///
+/// ```rust
+/// # use std::{io::{Cursor, Read, Write}, net::TcpListener};
+/// # use image::ImageFormat;
+/// use image::DynamicImage;
+/// use snapr::fetchers::{http::HttpTileFetcher, IndividualTileFetcher};
+///
+/// # let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+/// # let address = listener.local_addr().unwrap();
+/// #
+/// # std::thread::spawn(move || {
+/// #     let mut tile = Vec::new();
+/// #     DynamicImage::new_rgba8(256, 256).write_to(&mut Cursor::new(&mut tile), ImageFormat::Png).unwrap();
+/// #
+/// #     for stream in listener.incoming() {
+/// #         let mut stream = stream.unwrap();
+/// #         let mut request = [0; 4096];
+/// #         let length = stream.read(&mut request).unwrap();
+/// #         let request = String::from_utf8_lossy(&request[..length]);
+/// #
+/// #         let response = match request.starts_with("GET /1/0/1@2x.png?key=secret ") && request.contains("x-tenant: example") {
+/// #             true => [format!("HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: {}\r\n\r\n", tile.len()).into_bytes(), tile.clone()].concat(),
+/// #             false => b"HTTP/1.1 404 Not Found\r\nConnection: close\r\nContent-Length: 0\r\n\r\n".to_vec(),
+/// #         };
+/// #
+/// #         stream.write_all(&response).unwrap();
+/// #     }
+/// # });
+/// #
+/// let tile_fetcher = HttpTileFetcher::new(format!("http://{address}/{{z}}/{{x}}/{{y}}{{r}}.png"))
+///     .with_retina(true)
+///     .with_header("X-Tenant", "example")
+///     .unwrap()
+///     .with_query_param("key", "secret");
+///
+/// assert!(tile_fetcher.fetch_tile(0, 1, 1).is_ok());
+/// assert!(matches!(tile_fetcher.fetch_tile(1, 1, 1), Err(snapr::Error::TileNotFound { .. })));
+/// ```
+#[derive(Clone, Debug)]
+pub struct HttpTileFetcher {
+    template: String,
+    subdomains: Vec<String>,
+    retina: bool,
+    headers: HeaderMap,
+    query_params: Vec<(String, String)>,
+    user_agent: String,
+    client: OnceLock<reqwest::blocking::Client>,
+
+    #[cfg(feature = "tokio")]
+    async_client: OnceLock<reqwest::Client>,
+}
+
+impl HttpTileFetcher {
+    /// Constructs a new [`HttpTileFetcher`] requesting tiles from URLs following the given `template`.
+    pub fn new<T: Into<String>>(template: T) -> Self {
+        Self {
+            template: template.into(),
+            subdomains: Vec::new(),
+            retina: false,
+            headers: HeaderMap::new(),
+            query_params: Vec::new(),
+            user_agent: format!("snapr/{version}", version = env!("CARGO_PKG_VERSION")),
+            client: OnceLock::new(),
+
+            #[cfg(feature = "tokio")]
+            async_client: OnceLock::new(),
+        }
+    }
+
+    /// Configures the subdomains used to fill the `{s}` placeholder, spreading requests across them.
+    pub fn with_subdomains<I, S>(self, subdomains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            subdomains: subdomains.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+
+    /// Configures whether the `{r}` placeholder requests `@2x` retina tiles.
+    /// Retina tiles are typically used alongside a [`pixel_ratio`](crate::SnaprBuilder::with_pixel_ratio) of `2.0`.
+    pub fn with_retina(self, retina: bool) -> Self {
+        Self { retina, ..self }
+    }
+
+    /// Attempts to add a header sent along with every request.
+    /// Fails with [`Error::InvalidHeader`] if the `name` or `value` of the header isn't valid in HTTP.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use snapr::fetchers::http::HttpTileFetcher;
+    ///
+    /// let tile_fetcher = HttpTileFetcher::new("https://tile.osm.org/{z}/{x}/{y}.png");
+    ///
+    /// assert!(tile_fetcher.clone().with_header("X-Tenant", "example").is_ok());
+    /// assert!(matches!(tile_fetcher.with_header("X Tenant", "example"), Err(snapr::Error::InvalidHeader { .. })));
+    /// ```
+    pub fn with_header<N: AsRef<str>, V: AsRef<str>>(
+        mut self,
+        name: N,
+        value: V,
+    ) -> Result<Self, Error> {
+        let (name, value) = (name.as_ref(), value.as_ref());
+
+        let invalid = || Error::InvalidHeader {
+            name: name.to_string(),
+        };
+
+        let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid())?;
+        let header_value = HeaderValue::from_str(value).map_err(|_| invalid())?;
+
+        self.headers.append(header_name, header_value);
+        Ok(self)
+    }
+
+    /// Adds a query parameter appended to every request, such as an API key.
+    pub fn with_query_param<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.query_params.push((name.into(), value.into()));
+        self
+    }
+
+    /// Configures the `User-Agent` header sent along with every request, defaults to `snapr/{version}`.
+    pub fn with_user_agent<U: Into<String>>(self, user_agent: U) -> Self {
+        Self {
+            user_agent: user_agent.into(),
+            ..self
+        }
+    }
+
+    /// Attempts to return the URL, without query parameters, that the tile at the given `x`, `y`, and `zoom` values is requested from.
+    /// Fails with [`Error::MissingSubdomains`] if the template contains a `{s}` placeholder, but no [`subdomains`](Self::with_subdomains) were configured.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use snapr::fetchers::http::HttpTileFetcher;
+    ///
+    /// let tile_fetcher = HttpTileFetcher::new("https://{s}.tile.osm.org/{z}/{x}/{y}.png");
+    /// assert!(matches!(tile_fetcher.tile_url(1, 0, 1), Err(snapr::Error::MissingSubdomains)));
+    ///
+    /// let tile_fetcher = tile_fetcher.with_subdomains(["a", "b"]);
+    /// assert_eq!(tile_fetcher.tile_url(1, 0, 1).unwrap(), "https://b.tile.osm.org/1/1/0.png");
+    /// ```
+    pub fn tile_url(&self, x: i32, y: i32, zoom: u8) -> Result<String, Error> {
+        let coord = TileCoord::new(x, y, zoom);
+        let mut template = self
+            .template
+            .replace("{r}", if self.retina { "@2x" } else { "" });
+
+        if template.contains("{s}") {
+            if self.subdomains.is_empty() {
+                return Err(Error::MissingSubdomains);
+            }
+
+            let index = (x as i64 + y as i64).unsigned_abs() as usize % self.subdomains.len();
+            template = template.replace("{s}", &self.subdomains[index]);
+        }
+
+        coord.fill_template(&template)
+    }
+
+    fn client(&self) -> Result<&reqwest::blocking::Client, Error> {
+        if let Some(client) = self.client.get() {
+            return Ok(client);
+        }
+
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(&self.user_agent)
+            .default_headers(self.headers.clone())
+            .build()?;
+
+        Ok(self.client.get_or_init(|| client))
+    }
+
+    #[cfg(feature = "tokio")]
+    fn async_client(&self) -> Result<&reqwest::Client, Error> {
+        if let Some(client) = self.async_client.get() {
+            return Ok(client);
+        }
+
+        let client = reqwest::Client::builder()
+            .user_agent(&self.user_agent)
+            .default_headers(self.headers.clone())
+            .build()?;
+
+        Ok(self.async_client.get_or_init(|| client))
+    }
+}
+
+impl IndividualTileFetcher for HttpTileFetcher {
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        let response = self
+            .client()?
+            .get(self.tile_url(x, y, zoom)?)
+            .query(&self.query_params)
+            .send()?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(Error::TileNotFound { x, y, zoom });
+        }
+
+        let bytes = response.error_for_status()?.bytes()?;
+        Ok(image::load_from_memory(&bytes)?)
+    }
+}
+
+/// Requests tiles through an asynchronous HTTP client, separate from the one used by [`IndividualTileFetcher::fetch_tile`].
+///
+/// ## Example
+///
+/// ```rust
+/// # use std::{io::{Cursor, Read, Write}, net::TcpListener};
+/// # use image::ImageFormat;
+/// use image::DynamicImage;
+/// use snapr::fetchers::{http::HttpTileFetcher, AsyncIndividualTileFetcher};
+///
+/// # let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+/// # let address = listener.local_addr().unwrap();
+/// #
+/// # std::thread::spawn(move || {
+/// #     let mut tile = Vec::new();
+/// #     DynamicImage::new_rgba8(256, 256).write_to(&mut Cursor::new(&mut tile), ImageFormat::Png).unwrap();
+/// #
+/// #     for stream in listener.incoming() {
+/// #         let mut stream = stream.unwrap();
+/// #         let mut request = [0; 4096];
+/// #         let length = stream.read(&mut request).unwrap();
+/// #         let request = String::from_utf8_lossy(&request[..length]);
+/// #
+/// #         let response = match request.starts_with("GET /1/0/1@2x.png?key=secret ") && request.contains("x-tenant: example") {
+/// #             true => [format!("HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: {}\r\n\r\n", tile.len()).into_bytes(), tile.clone()].concat(),
+/// #             false => b"HTTP/1.1 404 Not Found\r\nConnection: close\r\nContent-Length: 0\r\n\r\n".to_vec(),
+/// #         };
+/// #
+/// #         stream.write_all(&response).unwrap();
+/// #     }
+/// # });
+/// #
+/// let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+///
+/// runtime.block_on(async {
+///     let tile_fetcher = HttpTileFetcher::new(format!("http://{address}/{{z}}/{{x}}/{{y}}{{r}}.png"))
+///         .with_retina(true)
+///         .with_header("X-Tenant", "example")
+///         .unwrap()
+///         .with_query_param("key", "secret");
+///
+///     assert!(AsyncIndividualTileFetcher::fetch_tile(&tile_fetcher, 0, 1, 1).await.is_ok());
+///
+///     let missing = AsyncIndividualTileFetcher::fetch_tile(&tile_fetcher, 1, 1, 1).await;
+///     assert!(matches!(missing, Err(snapr::Error::TileNotFound { .. })));
+/// });
+/// ```
+#[cfg(feature = "tokio")]
+#[async_trait::async_trait]
+impl super::AsyncIndividualTileFetcher for HttpTileFetcher {
+    async fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        let response = self
+            .async_client()?
+            .get(self.tile_url(x, y, zoom)?)
+            .query(&self.query_params)
+            .send()
+            .await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(Error::TileNotFound { x, y, zoom });
+        }
+
+        let bytes = response.error_for_status()?.bytes().await?;
+        Ok(image::load_from_memory(&bytes)?)
+    }
+}
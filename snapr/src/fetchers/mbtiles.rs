@@ -0,0 +1,219 @@
+//! Contains a tile fetcher reading raster tiles from [MBTiles](https://github.com/mapbox/mbtiles-spec) files.
+//! See [`MbTiles`] for more details.
+
+use std::{
+    fmt,
+    path::Path,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use image::DynamicImage;
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+
+use crate::Error;
+
+use super::{BatchTileFetcher, IndividualTileFetcher, TileCoord};
+
+pub use rusqlite;
+
+/// Tile fetcher reading raster tiles from an [MBTiles](https://github.com/mapbox/mbtiles-spec) file.
+///
+/// MBTiles files address tiles with the TMS scheme, rows are flipped accordingly before being looked up.
+/// Tiles outside of the [`minzoom`](MbTilesMetadata::minzoom) and [`maxzoom`](MbTilesMetadata::maxzoom) of the file, or missing from it, are reported as [`Error::TileNotFound`].
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use snapr::{fetchers::mbtiles::MbTiles, SnaprBuilder, TileFetcher};
+///
+/// let mbtiles = MbTiles::open("basemap.mbtiles").unwrap();
+/// println!("{:?}", mbtiles.metadata().attribution);
+///
+/// let snapr = SnaprBuilder::new()
+///     .with_tile_fetcher(TileFetcher::individual(mbtiles))
+///     .build();
+/// ```
+pub struct MbTiles {
+    connection: Mutex<Connection>,
+    metadata: MbTilesMetadata,
+}
+
+/// Metadata of an [`MbTiles`] file, read from its `metadata` table.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MbTilesMetadata {
+    /// Human-readable name of the tileset.
+    pub name: Option<String>,
+
+    /// File format of the tiles, such as `png` or `jpg`.
+    pub format: Option<String>,
+
+    /// Lowest zoom level with tiles in the tileset.
+    pub minzoom: Option<u8>,
+
+    /// Highest zoom level with tiles in the tileset.
+    pub maxzoom: Option<u8>,
+
+    /// Attribution to display alongside the tiles, possibly containing HTML.
+    pub attribution: Option<String>,
+}
+
+impl MbTiles {
+    /// Opens the [MBTiles](https://github.com/mapbox/mbtiles-spec) file at the given `path` as read-only.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let connection = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+
+        Self::from_connection(connection)
+    }
+
+    /// Constructs a new [`MbTiles`] from an existing `connection` to an [MBTiles](https://github.com/mapbox/mbtiles-spec) database.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    ///
+    /// use image::{DynamicImage, ImageFormat};
+    /// use snapr::fetchers::{mbtiles::{rusqlite::Connection, MbTiles}, IndividualTileFetcher};
+    ///
+    /// let connection = Connection::open_in_memory().unwrap();
+    ///
+    /// connection.execute_batch("
+    ///     CREATE TABLE metadata (name TEXT, value TEXT);
+    ///     CREATE TABLE tiles (zoom_level INTEGER, tile_column INTEGER, tile_row INTEGER, tile_data BLOB);
+    ///     INSERT INTO metadata VALUES ('minzoom', '0'), ('maxzoom', '1'), ('attribution', 'Example');
+    /// ").unwrap();
+    ///
+    /// let mut tile = Vec::new();
+    /// DynamicImage::new_rgba8(256, 256).write_to(&mut Cursor::new(&mut tile), ImageFormat::Png).unwrap();
+    ///
+    /// // The TMS row `1` at zoom level `1` is the XYZ row `0`.
+    /// connection.execute("INSERT INTO tiles VALUES (1, 1, 1, ?1)", [&tile]).unwrap();
+    ///
+    /// let mbtiles = MbTiles::from_connection(connection).unwrap();
+    ///
+    /// assert_eq!(mbtiles.metadata().attribution.as_deref(), Some("Example"));
+    /// assert!(mbtiles.fetch_tile(1, 0, 1).is_ok());
+    /// assert!(mbtiles.fetch_tile(1, 1, 1).is_err());
+    /// assert!(mbtiles.fetch_tile(1, 0, 2).is_err());
+    /// ```
+    pub fn from_connection(connection: Connection) -> Result<Self, Error> {
+        let mut metadata = MbTilesMetadata::default();
+
+        {
+            let mut statement = connection.prepare("SELECT name, value FROM metadata")?;
+            let mut rows = statement.query([])?;
+
+            while let Some(row) = rows.next()? {
+                let (name, value): (String, String) = (row.get(0)?, row.get(1)?);
+
+                match name.as_str() {
+                    "name" => metadata.name = Some(value),
+                    "format" => metadata.format = Some(value),
+                    "minzoom" => metadata.minzoom = value.trim().parse().ok(),
+                    "maxzoom" => metadata.maxzoom = value.trim().parse().ok(),
+                    "attribution" => metadata.attribution = Some(value),
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+            metadata,
+        })
+    }
+
+    /// Returns the [`MbTilesMetadata`] read when the file was opened.
+    pub fn metadata(&self) -> &MbTilesMetadata {
+        &self.metadata
+    }
+
+    /// Returns the encoded data of the tile at the given XYZ `x`, `y`, and `zoom` values, or [`None`] if the file doesn't contain it.
+    pub fn tile_data(&self, x: i32, y: i32, zoom: u8) -> Result<Option<Vec<u8>>, Error> {
+        self.query_tile_data(&self.connection(), x, y, zoom)
+    }
+
+    fn query_tile_data(
+        &self,
+        connection: &Connection,
+        x: i32,
+        y: i32,
+        zoom: u8,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let MbTilesMetadata {
+            minzoom, maxzoom, ..
+        } = self.metadata;
+
+        if minzoom.is_some_and(|minzoom| zoom < minzoom)
+            || maxzoom.is_some_and(|maxzoom| zoom > maxzoom)
+        {
+            return Ok(None);
+        }
+
+        let mut statement = connection.prepare_cached(
+            "SELECT tile_data FROM tiles WHERE zoom_level = ?1 AND tile_column = ?2 AND tile_row = ?3",
+        )?;
+
+        let tile_data = statement
+            .query_row((zoom, x, TileCoord::new(x, y, zoom).tms_y()?), |row| {
+                row.get(0)
+            })
+            .optional()?;
+
+        Ok(tile_data)
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        // Connections hold no state of ours, so a panic while holding the lock can't leave it inconsistent.
+        self.connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl IndividualTileFetcher for MbTiles {
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        match self.tile_data(x, y, zoom)? {
+            Some(tile_data) => Ok(image::load_from_memory(&tile_data)?),
+            None => Err(Error::TileNotFound { x, y, zoom }),
+        }
+    }
+}
+
+/// Tiles missing from the file are left out of the returned tiles, rather than failing the whole batch.
+impl BatchTileFetcher for MbTiles {
+    fn fetch_tiles(
+        &self,
+        coordinate_matrix: &[(i32, i32)],
+        zoom: u8,
+    ) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+        let encoded_tiles = {
+            let connection = self.connection();
+            let mut encoded_tiles = Vec::with_capacity(coordinate_matrix.len());
+
+            for &(x, y) in coordinate_matrix {
+                if let Some(tile_data) = self.query_tile_data(&connection, x, y, zoom)? {
+                    encoded_tiles.push((x, y, tile_data));
+                }
+            }
+
+            encoded_tiles
+        };
+
+        encoded_tiles
+            .into_iter()
+            .map(|(x, y, tile_data)| Ok((x, y, image::load_from_memory(&tile_data)?)))
+            .collect()
+    }
+}
+
+impl fmt::Debug for MbTiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MbTiles")
+            .field("metadata", &self.metadata)
+            .finish()
+    }
+}
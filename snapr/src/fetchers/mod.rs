@@ -3,12 +3,262 @@
 
 #[cfg(feature = "tokio")]
 use std::future::Future;
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
 
 use image::DynamicImage;
 
 use crate::Error;
 
+pub mod cache;
+pub mod directory;
+pub mod disk_cache;
+pub mod fallback;
+
+#[cfg(feature = "http")]
+pub mod http;
+
+#[cfg(feature = "mbtiles")]
+pub mod mbtiles;
+
+#[cfg(feature = "mvt")]
+pub mod mvt;
+
+pub mod overzoom;
+
+#[cfg(feature = "pmtiles")]
+pub mod pmtiles;
+
+pub mod retry;
+
+/// Addressing schemes used by tile providers to identify a tile.
+/// See [`TileCoord::address`] for more details.
+///
+/// Tile fetchers always receive [`TileScheme::Xyz`] coordinates, fetchers of servers using another scheme convert them through [`TileCoord`],
+/// either by hand or by being wrapped in a [`TileCoordFetcher`]. Template based fetchers, such as [`DirectoryTileFetcher`](directory::DirectoryTileFetcher),
+/// select the scheme through the placeholders of their template instead, see [`TileCoord::fill_template`].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum TileScheme {
+    /// Google-style `{z}/{x}/{y}` addressing, where rows are counted from the top of the tile matrix.
+    #[default]
+    Xyz,
+
+    /// [TMS](https://wiki.osgeo.org/wiki/Tile_Map_Service_Specification) `{z}/{x}/{y}` addressing, where rows are counted from the bottom of the tile matrix.
+    Tms,
+
+    /// Bing-style [quadkey](https://learn.microsoft.com/en-us/bingmaps/articles/bing-maps-tile-system) addressing, where each digit selects a quadrant of the parent tile.
+    Quadkey,
+}
+
+/// Position of a tile within the tile matrix, with rows counted from the top as in the [`TileScheme::Xyz`] scheme.
+/// Provides conversions to the other [`TileSchemes`](TileScheme), which fail with [`Error::InvalidTileCoord`] above [`TileCoord::MAX_ZOOM`],
+/// or when the tile lies outside of the tile matrix.
+///
+/// ## Example
+///
+/// ```rust
+/// use snapr::fetchers::{TileCoord, TileScheme};
+///
+/// let coord = TileCoord::new(3, 5, 3);
+///
+/// assert_eq!(coord.tms_y().unwrap(), 2);
+/// assert_eq!(coord.quadkey().unwrap(), "213");
+/// assert_eq!(coord.address(TileScheme::Tms).unwrap(), "3/3/2");
+/// assert_eq!(TileCoord::from_quadkey("213"), Some(coord));
+///
+/// assert!(TileCoord::new(0, 0, 32).tms_y().is_err());
+/// assert!(TileCoord::new(0, 5, 1).tms_y().is_err());
+/// assert!(TileCoord::new(-1, 0, 1).quadkey().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct TileCoord {
+    pub x: i32,
+    pub y: i32,
+    pub z: u8,
+}
+
+impl TileCoord {
+    /// Deepest zoom level whose tiles can be converted to other [`TileSchemes`](TileScheme), as the tile matrix no longer fits within [`i32s`](i32) past it.
+    pub const MAX_ZOOM: u8 = 30;
+
+    /// Constructs a new [`TileCoord`] from [`TileScheme::Xyz`] coordinates.
+    pub fn new(x: i32, y: i32, z: u8) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Attempts to construct a new [`TileCoord`] from [`TileScheme::Tms`] coordinates.
+    pub fn from_tms(x: i32, y: i32, z: u8) -> Result<Self, Error> {
+        let coord = Self::new(x, y, z);
+        let y = coord.flip_y()?;
+
+        Ok(Self { y, ..coord })
+    }
+
+    /// Attempts to construct a new [`TileCoord`] from a [`TileScheme::Quadkey`], returning [`None`] if the `quadkey` contains anything but the digits `0` to `3`,
+    /// or is longer than [`MAX_ZOOM`](Self::MAX_ZOOM) digits.
+    pub fn from_quadkey(quadkey: &str) -> Option<Self> {
+        let z = u8::try_from(quadkey.len())
+            .ok()
+            .filter(|&z| z <= Self::MAX_ZOOM)?;
+        let mut coord = Self::new(0, 0, z);
+
+        for digit in quadkey.chars() {
+            let quadrant = digit.to_digit(4)? as i32;
+
+            coord.x = (coord.x << 1) | (quadrant & 1);
+            coord.y = (coord.y << 1) | (quadrant >> 1);
+        }
+
+        Some(coord)
+    }
+
+    /// Attempts to return the row of the tile as counted from the bottom of the tile matrix, as used by [`TileScheme::Tms`].
+    pub fn tms_y(&self) -> Result<i32, Error> {
+        self.flip_y()
+    }
+
+    /// Attempts to return the [`TileScheme::Quadkey`] of the tile.
+    pub fn quadkey(&self) -> Result<String, Error> {
+        self.validate()?;
+
+        let quadkey = (1..=self.z)
+            .rev()
+            .map(|level| {
+                let mask = 1 << (level - 1);
+                let quadrant = (self.x & mask != 0) as u8 | ((self.y & mask != 0) as u8) << 1;
+
+                char::from(b'0' + quadrant)
+            })
+            .collect();
+
+        Ok(quadkey)
+    }
+
+    /// Attempts to format the tile's address in the given `scheme`, `{z}/{x}/{y}` for [`TileScheme::Xyz`] and [`TileScheme::Tms`], or the quadkey for [`TileScheme::Quadkey`].
+    pub fn address(&self, scheme: TileScheme) -> Result<String, Error> {
+        match scheme {
+            TileScheme::Xyz => Ok(self.to_string()),
+            TileScheme::Tms => Ok(format!(
+                "{z}/{x}/{y}",
+                z = self.z,
+                x = self.x,
+                y = self.tms_y()?
+            )),
+            TileScheme::Quadkey => self.quadkey(),
+        }
+    }
+
+    /// Attempts to fill the placeholders of a tile address `template` with the tile's position.
+    ///
+    /// Supports `{z}`, `{x}`, and `{y}` for [`TileScheme::Xyz`] coordinates, `{-y}` for the [`TileScheme::Tms`] row, and `{q}` for the [`TileScheme::Quadkey`].
+    /// Fails when the `template` contains a placeholder of a scheme the tile can't be converted to.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use snapr::fetchers::TileCoord;
+    ///
+    /// let coord = TileCoord::new(3, 5, 3);
+    ///
+    /// assert_eq!(coord.fill_template("/tiles/{z}/{x}/{y}.png").unwrap(), "/tiles/3/3/5.png");
+    /// assert_eq!(coord.fill_template("/tiles/{z}/{x}/{-y}.png").unwrap(), "/tiles/3/3/2.png");
+    /// assert_eq!(coord.fill_template("/tiles/{q}.jpeg").unwrap(), "/tiles/213.jpeg");
+    /// ```
+    pub fn fill_template(&self, template: &str) -> Result<String, Error> {
+        let mut address = template
+            .replace("{z}", &self.z.to_string())
+            .replace("{x}", &self.x.to_string())
+            .replace("{y}", &self.y.to_string());
+
+        if address.contains("{-y}") {
+            address = address.replace("{-y}", &self.tms_y()?.to_string());
+        }
+
+        if address.contains("{q}") {
+            address = address.replace("{q}", &self.quadkey()?);
+        }
+
+        Ok(address)
+    }
+
+    /// Returns [`Error::InvalidTileCoord`] if the tile is past [`MAX_ZOOM`](Self::MAX_ZOOM), or outside of the tile matrix.
+    fn validate(&self) -> Result<(), Error> {
+        if self.z > Self::MAX_ZOOM {
+            return Err(self.invalid());
+        }
+
+        let size = 1_i32 << self.z;
+
+        match (0..size).contains(&self.x) && (0..size).contains(&self.y) {
+            true => Ok(()),
+            false => Err(self.invalid()),
+        }
+    }
+
+    /// Flips the row of the tile between the [`TileScheme::Xyz`] and [`TileScheme::Tms`] schemes, which is its own inverse.
+    fn flip_y(&self) -> Result<i32, Error> {
+        self.validate()?;
+        Ok((1_i32 << self.z) - 1 - self.y)
+    }
+
+    fn invalid(&self) -> Error {
+        Error::InvalidTileCoord {
+            x: self.x,
+            y: self.y,
+            zoom: self.z,
+        }
+    }
+}
+
+impl From<(i32, i32, u8)> for TileCoord {
+    fn from((x, y, z): (i32, i32, u8)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl fmt::Display for TileCoord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{z}/{x}/{y}", z = self.z, x = self.x, y = self.y)
+    }
+}
+
+/// Adapts a function taking a [`TileCoord`] into an [`IndividualTileFetcher`], sparing it from converting the tile's position by hand.
+///
+/// ## Example
+///
+/// ```rust
+/// use image::DynamicImage;
+/// use snapr::{fetchers::{TileCoord, TileCoordFetcher}, Error, TileFetcher};
+///
+/// fn tile_fetcher(coord: TileCoord) -> Result<DynamicImage, Error> {
+///     let address = format!("https://tiles.example.com/{quadkey}.png", quadkey = coord.quadkey()?);
+///     todo!("fetch tile's image from {address}")
+/// }
+///
+/// let fetcher = TileFetcher::individual(TileCoordFetcher(tile_fetcher));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TileCoordFetcher<F>(pub F);
+
+#[cfg(feature = "rayon")]
+impl<F> IndividualTileFetcher for TileCoordFetcher<F>
+where
+    F: Fn(TileCoord) -> Result<DynamicImage, Error> + Sync,
+{
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        (self.0)(TileCoord::new(x, y, zoom))
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+impl<F> IndividualTileFetcher for TileCoordFetcher<F>
+where
+    F: Fn(TileCoord) -> Result<DynamicImage, Error>,
+{
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        (self.0)(TileCoord::new(x, y, zoom))
+    }
+}
+
 /// Types that represent objects that can fetch map tiles one-by-one with the tile's [`EPSG:3857`](https://epsg.io/3857) position.
 ///
 /// ## Example
@@ -208,6 +458,18 @@ where
     }
 }
 
+#[cfg(feature = "tokio")]
+#[async_trait::async_trait]
+impl<A, F> AsyncIndividualTileFetcher for TileCoordFetcher<F>
+where
+    A: Future<Output = Result<DynamicImage, Error>> + Send,
+    F: (Fn(TileCoord) -> A) + Send + Sync,
+{
+    async fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        (self.0)(TileCoord::new(x, y, zoom)).await
+    }
+}
+
 /// Types that represent objects that can fetch map tiles all at once with each tile's [`EPSG:3857`](https://epsg.io/3857) position.
 ///
 /// ## Example
@@ -314,9 +576,15 @@ impl<'a> AsyncTileFetcher<'a> {
     }
 }
 
+/// Tile returned by [`AsyncTileFetcher::fetch_tiles_in_batch`], alongside the result of fetching it.
+#[cfg(feature = "tokio")]
+pub(crate) type FetchedTile = (i32, i32, Result<DynamicImage, Error>);
+
 #[cfg(feature = "tokio")]
 impl<'a> AsyncTileFetcher<'a> {
     /// Retrieves tiles from the [`AsyncTileFetcher`] with an [`AsyncBatchTileFetcher`] executor.
+    ///
+    /// Tiles of an [`AsyncIndividualTileFetcher`] are returned alongside their own result, so a single tile failing to be fetched doesn't fail the others.
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(level = "TRACE", skip(self), err)
@@ -325,7 +593,7 @@ impl<'a> AsyncTileFetcher<'a> {
         &self,
         coordinate_matrix: &[(i32, i32)],
         zoom: u8,
-    ) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+    ) -> Result<Vec<FetchedTile>, Error> {
         use tokio::task::JoinSet;
 
         let expected_tile_count = coordinate_matrix.len();
@@ -355,10 +623,7 @@ impl<'a> AsyncTileFetcher<'a> {
                         );
                     }
 
-                    tasks.spawn(async move {
-                        let tile = tile_fetcher.fetch_tile(x, y, zoom).await;
-                        tile.map(|tile| (x, y, tile))
-                    });
+                    tasks.spawn(async move { (x, y, tile_fetcher.fetch_tile(x, y, zoom).await) });
                 }
 
                 #[cfg(feature = "tracing")]
@@ -370,13 +635,14 @@ impl<'a> AsyncTileFetcher<'a> {
                 }
 
                 while let Some(task) = tasks.join_next().await {
-                    let tile = task.map_err(|_| Error::AsynchronousTaskPanic)??;
+                    let tile = task.map_err(|_| Error::AsynchronousTaskPanic)?;
 
                     #[cfg(feature = "tracing")]
                     {
                         tracing::trace!(
                             tile = ?(tile.0, tile.1),
-                            "successfully retrieved tile from `AsyncIndividualTileFetcher.fetch_tile` task"
+                            fetched = tile.2.is_ok(),
+                            "retrieved tile from `AsyncIndividualTileFetcher.fetch_tile` task"
                         );
                     }
 
@@ -388,7 +654,12 @@ impl<'a> AsyncTileFetcher<'a> {
 
             AsyncTileFetcher::Batch(tile_fetcher) => {
                 let coordinate_matrix = Vec::from(coordinate_matrix);
-                tile_fetcher.fetch_tiles(coordinate_matrix, zoom).await
+                let tiles = tile_fetcher.fetch_tiles(coordinate_matrix, zoom).await?;
+
+                Ok(tiles
+                    .into_iter()
+                    .map(|(x, y, tile)| (x, y, Ok(tile)))
+                    .collect())
             }
         }
     }
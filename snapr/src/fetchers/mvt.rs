@@ -0,0 +1,886 @@
+//! Contains a tile fetcher rasterizing [Mapbox Vector Tiles](https://github.com/mapbox/vector-tile-spec) into raster tiles.
+//! See [`VectorTileFetcher`] for more details.
+
+use std::{collections::HashMap, fmt, io::Read, path::PathBuf, sync::Arc};
+
+use flate2::read::GzDecoder;
+use image::DynamicImage;
+use tiny_skia::{Color, FillRule, Paint, PathBuilder, Pixmap, Stroke, Transform};
+
+use crate::{pixmap_to_image, Error};
+
+use super::{IndividualTileFetcher, TileCoord};
+
+#[cfg(feature = "svg")]
+use crate::drawing::svg::Label;
+
+/// Leading bytes of gzip compressed data.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Predicate deciding whether a rule applies to a feature, see [`VectorRule::with_filter`].
+type Filter = Arc<dyn Fn(&VectorFeature) -> bool + Send + Sync>;
+
+/// Tile fetcher reading [Mapbox Vector Tiles](https://github.com/mapbox/vector-tile-spec) from a local directory, and rasterizing them with a [`VectorStyle`].
+///
+/// Paths of tiles are built like [`DirectoryTileFetcher`](super::directory::DirectoryTileFetcher) paths, through [`TileCoord::fill_template`].
+/// Tiles can be stored raw, or compressed with gzip as commonly done by tile generators.
+/// Tiles are rasterized independently of each other, meaning labels near the edge of a tile are cut off.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use snapr::{
+///     fetchers::mvt::{VectorRule, VectorStyle, VectorTileFetcher},
+///     tiny_skia::Color,
+///     SnaprBuilder, TileFetcher,
+/// };
+///
+/// let style = VectorStyle::new()
+///     .with_background(Color::from_rgba8(242, 239, 233, 255))
+///     .with_rule(VectorRule::fill("water", Color::from_rgba8(170, 211, 223, 255)))
+///     .with_rule(VectorRule::line("transportation", Color::WHITE, 2.0));
+///
+/// let tile_fetcher = VectorTileFetcher::new("tiles/{z}/{x}/{y}.pbf", style);
+///
+/// let snapr = SnaprBuilder::new()
+///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct VectorTileFetcher {
+    template: String,
+    style: VectorStyle,
+    tile_size: u32,
+}
+
+impl VectorTileFetcher {
+    /// Constructs a new [`VectorTileFetcher`] reading tiles from paths following the given `template`, and rasterizing them with the given `style`.
+    /// Tiles are rasterized to `256` pixels wide and tall, unless configured otherwise with [`with_tile_size`](Self::with_tile_size).
+    pub fn new<T: Into<String>>(template: T, style: VectorStyle) -> Self {
+        Self {
+            template: template.into(),
+            style,
+            tile_size: 256,
+        }
+    }
+
+    /// Configures the width and height, in pixels, tiles are rasterized to.
+    /// Matching the `tile_size` multiplied by the `pixel_ratio` of the [`Snapr`](crate::Snapr) avoids resampling the rasterized tiles.
+    pub fn with_tile_size(self, tile_size: u32) -> Self {
+        Self { tile_size, ..self }
+    }
+
+    /// Attempts to return the path the tile at the given `x`, `y`, and `zoom` values is read from.
+    pub fn tile_path(&self, x: i32, y: i32, zoom: u8) -> Result<PathBuf, Error> {
+        let path = TileCoord::new(x, y, zoom).fill_template(&self.template)?;
+        Ok(PathBuf::from(path))
+    }
+
+    /// Decodes and rasterizes the given encoded `data` of a vector tile, which may be compressed with gzip.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use snapr::{
+    ///     fetchers::mvt::{VectorRule, VectorStyle, VectorTileFetcher},
+    ///     tiny_skia::Color,
+    /// };
+    ///
+    /// // Tile with a single `water` layer, containing a polygon covering the whole tile.
+    /// let data = [
+    ///     26, 32, 120, 2, 10, 5, 119, 97, 116, 101, 114, 18, 18, 24, 3, 34, 14, 9, 0, 0, 26, 128, 64,
+    ///     0, 0, 128, 64, 255, 63, 0, 15, 40, 128, 32,
+    /// ];
+    ///
+    /// let style = VectorStyle::new().with_rule(VectorRule::fill("water", Color::from_rgba8(0, 0, 255, 255)));
+    /// let tile = VectorTileFetcher::new("{z}/{x}/{y}.pbf", style).render(&data).unwrap();
+    ///
+    /// assert_eq!(tile.to_rgba8().get_pixel(128, 128).0, [0, 0, 255, 255]);
+    /// ```
+    pub fn render(&self, data: &[u8]) -> Result<DynamicImage, Error> {
+        self.render_tile(&VectorTile::decode(data)?)
+    }
+
+    /// Rasterizes the given decoded `tile` with the [`VectorStyle`] of the [`VectorTileFetcher`].
+    pub fn render_tile(&self, tile: &VectorTile) -> Result<DynamicImage, Error> {
+        let Some(mut pixmap) = Pixmap::new(self.tile_size, self.tile_size) else {
+            return Err(Error::PixmapConstruction);
+        };
+
+        if let Some(background) = self.style.background {
+            pixmap.fill(background);
+        }
+
+        for rule in &self.style.rules {
+            for layer in tile
+                .layers
+                .iter()
+                .filter(|layer| layer.name == rule.source_layer)
+            {
+                let scale = self.tile_size as f32 / layer.extent as f32;
+
+                for feature in &layer.features {
+                    if rule.filter.as_ref().is_some_and(|filter| !filter(feature)) {
+                        continue;
+                    }
+
+                    rule.paint.render(&mut pixmap, feature, scale)?;
+                }
+            }
+        }
+
+        Ok(DynamicImage::from(pixmap_to_image(&pixmap)))
+    }
+}
+
+impl IndividualTileFetcher for VectorTileFetcher {
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        match std::fs::read(self.tile_path(x, y, zoom)?) {
+            Ok(data) => self.render(&data),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                Err(Error::TileNotFound { x, y, zoom })
+            }
+
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+/// Style used by a [`VectorTileFetcher`] to rasterize vector tiles, made of an optional background color and an ordered list of [`VectorRules`](VectorRule).
+///
+/// Rules are rendered in the order they were added, meaning later rules are painted on top of earlier ones.
+#[derive(Clone, Debug, Default)]
+pub struct VectorStyle {
+    background: Option<Color>,
+    rules: Vec<VectorRule>,
+}
+
+impl VectorStyle {
+    /// Constructs a new, empty, [`VectorStyle`] rasterizing tiles to transparent images.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configures the color tiles are filled with before any rule is rendered.
+    pub fn with_background(self, background: Color) -> Self {
+        Self {
+            background: Some(background),
+            ..self
+        }
+    }
+
+    /// Appends the given `rule` to the rules of the [`VectorStyle`], rendering it on top of the previously added rules.
+    pub fn with_rule(mut self, rule: VectorRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+}
+
+/// Rule rendering the features of a source layer of vector tiles, see [`VectorStyle`].
+///
+/// Widths and offsets are measured in pixels of the rasterized tile.
+#[derive(Clone)]
+pub struct VectorRule {
+    source_layer: String,
+    paint: VectorPaint,
+    filter: Option<Filter>,
+}
+
+#[derive(Clone, Debug)]
+enum VectorPaint {
+    Fill {
+        color: Color,
+    },
+
+    Line {
+        color: Color,
+        width: f32,
+    },
+
+    #[cfg(feature = "svg")]
+    Label {
+        property: String,
+        label: Label,
+    },
+}
+
+impl VectorRule {
+    /// Constructs a new [`VectorRule`] filling the polygons of the given `source_layer` with the given `color`.
+    pub fn fill<S: Into<String>>(source_layer: S, color: Color) -> Self {
+        Self::new(source_layer, VectorPaint::Fill { color })
+    }
+
+    /// Constructs a new [`VectorRule`] stroking the lines, and outlines of polygons, of the given `source_layer` with the given `color` and `width`.
+    pub fn line<S: Into<String>>(source_layer: S, color: Color, width: f32) -> Self {
+        Self::new(source_layer, VectorPaint::Line { color, width })
+    }
+
+    /// Constructs a new [`VectorRule`] labeling the features of the given `source_layer` with the value of their `property`, rendered with the given `label`.
+    /// Points are labeled at their position, while other geometries are labeled at their centroid.
+    /// Features without the `property` are left unlabeled.
+    #[cfg(feature = "svg")]
+    pub fn label<S: Into<String>, P: Into<String>>(
+        source_layer: S,
+        property: P,
+        label: Label,
+    ) -> Self {
+        Self::new(
+            source_layer,
+            VectorPaint::Label {
+                property: property.into(),
+                label,
+            },
+        )
+    }
+
+    /// Configures the predicate deciding which features of the source layer the [`VectorRule`] applies to.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use snapr::{fetchers::mvt::{PropertyValue, VectorRule}, tiny_skia::Color};
+    ///
+    /// let rule = VectorRule::line("transportation", Color::WHITE, 4.0).with_filter(|feature| {
+    ///     feature.properties.get("class") == Some(&PropertyValue::String("motorway".to_string()))
+    /// });
+    /// ```
+    pub fn with_filter<F>(self, filter: F) -> Self
+    where
+        F: Fn(&VectorFeature) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            filter: Some(Arc::new(filter)),
+            ..self
+        }
+    }
+
+    fn new<S: Into<String>>(source_layer: S, paint: VectorPaint) -> Self {
+        Self {
+            source_layer: source_layer.into(),
+            paint,
+            filter: None,
+        }
+    }
+}
+
+impl fmt::Debug for VectorRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VectorRule")
+            .field("source_layer", &self.source_layer)
+            .field("paint", &self.paint)
+            .finish()
+    }
+}
+
+impl VectorPaint {
+    /// Renders the given `feature` to the `pixmap`, scaling its coordinates from the extent of its layer by the given `scale`.
+    fn render(
+        &self,
+        pixmap: &mut Pixmap,
+        feature: &VectorFeature,
+        scale: f32,
+    ) -> Result<(), Error> {
+        let transform = Transform::from_scale(scale, scale);
+
+        match self {
+            VectorPaint::Fill { color } => {
+                let Some(path) = geometry_path(&feature.geometry, true) else {
+                    return Ok(());
+                };
+
+                let mut paint = Paint::default();
+                paint.set_color(*color);
+
+                pixmap.fill_path(&path, &paint, FillRule::EvenOdd, transform, None);
+            }
+
+            VectorPaint::Line { color, width } => {
+                let Some(path) = geometry_path(&feature.geometry, false) else {
+                    return Ok(());
+                };
+
+                let mut paint = Paint::default();
+                paint.set_color(*color);
+
+                // Strokes are transformed along with the path, so their width is given in the extent of the layer.
+                let stroke = Stroke {
+                    width: width / scale,
+                    ..Stroke::default()
+                };
+
+                pixmap.stroke_path(&path, &paint, &stroke, transform, None);
+            }
+
+            #[cfg(feature = "svg")]
+            VectorPaint::Label { property, label } => {
+                use geo::Centroid;
+
+                let Some(value) = feature.properties.get(property) else {
+                    return Ok(());
+                };
+
+                let positions = match &feature.geometry {
+                    geo::Geometry::Point(point) => vec![*point],
+                    geo::Geometry::MultiPoint(points) => points.0.clone(),
+                    geometry => geometry.centroid().into_iter().collect(),
+                };
+
+                let label = Label {
+                    text: escape_xml(&value.to_string()),
+                    ..label.clone()
+                };
+
+                for position in positions {
+                    let pixel = (
+                        (position.x() as f32 * scale).round() as i32,
+                        (position.y() as f32 * scale).round() as i32,
+                    );
+
+                    label.try_as_svg(pixel, 1.0)?.render(pixmap);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds a path from the lines and polygons of the given `geometry`, closing rings when `close_rings` is `true`.
+/// Returns [`None`] if the `geometry` doesn't contain any line or polygon.
+fn geometry_path(geometry: &geo::Geometry, close_rings: bool) -> Option<tiny_skia::Path> {
+    let mut builder = PathBuilder::new();
+
+    let mut push_line = |line: &geo::LineString, close: bool| {
+        let mut coords = line.coords();
+
+        if let Some(first) = coords.next() {
+            builder.move_to(first.x as f32, first.y as f32);
+            coords.for_each(|coord| builder.line_to(coord.x as f32, coord.y as f32));
+
+            if close {
+                builder.close();
+            }
+        }
+    };
+
+    let polygons = match geometry {
+        geo::Geometry::LineString(line) => {
+            push_line(line, false);
+            &[][..]
+        }
+
+        geo::Geometry::MultiLineString(lines) => {
+            lines.iter().for_each(|line| push_line(line, false));
+            &[][..]
+        }
+
+        geo::Geometry::Polygon(polygon) => std::slice::from_ref(polygon),
+        geo::Geometry::MultiPolygon(polygons) => &polygons.0[..],
+        _ => &[][..],
+    };
+
+    for polygon in polygons {
+        push_line(polygon.exterior(), close_rings);
+        polygon
+            .interiors()
+            .iter()
+            .for_each(|interior| push_line(interior, close_rings));
+    }
+
+    builder.finish()
+}
+
+/// Escapes the characters of the given `text` that are reserved in XML, as labels are rendered through SVGs.
+#[cfg(feature = "svg")]
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Decoded [Mapbox Vector Tile](https://github.com/mapbox/vector-tile-spec), made of named layers of features.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VectorTile {
+    pub layers: Vec<VectorLayer>,
+}
+
+/// Layer of a [`VectorTile`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VectorLayer {
+    /// Name of the layer, matched against the source layer of [`VectorRules`](VectorRule).
+    pub name: String,
+
+    /// Width and height of the tile in the coordinates of the features of the layer, typically `4096`.
+    pub extent: u32,
+
+    pub features: Vec<VectorFeature>,
+}
+
+/// Feature of a [`VectorLayer`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct VectorFeature {
+    pub id: Option<u64>,
+
+    /// Geometry of the feature, in the coordinates of its layer's [`extent`](VectorLayer::extent), with the `y` axis pointing down.
+    pub geometry: geo::Geometry,
+
+    pub properties: HashMap<String, PropertyValue>,
+}
+
+/// Value of a property of a [`VectorFeature`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    String(String),
+    Float(f64),
+    Int(i64),
+    UInt(u64),
+    Bool(bool),
+}
+
+impl fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyValue::String(value) => write!(f, "{value}"),
+            PropertyValue::Float(value) => write!(f, "{value}"),
+            PropertyValue::Int(value) => write!(f, "{value}"),
+            PropertyValue::UInt(value) => write!(f, "{value}"),
+            PropertyValue::Bool(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl VectorTile {
+    /// Decodes a [`VectorTile`] from the given encoded `data`, which may be compressed with a single layer of gzip.
+    /// Features of unknown geometry types are left out.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// use flate2::{write::GzEncoder, Compression};
+    /// use snapr::fetchers::mvt::VectorTile;
+    ///
+    /// fn varint(bytes: &mut Vec<u8>, mut value: u64) {
+    ///     while value >= 0x80 {
+    ///         bytes.push(value as u8 | 0x80);
+    ///         value >>= 7;
+    ///     }
+    ///
+    ///     bytes.push(value as u8);
+    /// }
+    ///
+    /// fn field(bytes: &mut Vec<u8>, field: u64, data: &[u8]) {
+    ///     varint(bytes, field << 3 | 2);
+    ///     varint(bytes, data.len() as u64);
+    ///     bytes.extend(data);
+    /// }
+    ///
+    /// // Tile of a single layer, containing a single point feature with the given geometry `commands`.
+    /// fn tile(commands: &[u64]) -> Vec<u8> {
+    ///     let mut packed = Vec::new();
+    ///     commands.iter().for_each(|&command| varint(&mut packed, command));
+    ///
+    ///     let mut feature = vec![3 << 3, 1];
+    ///     field(&mut feature, 4, &packed);
+    ///
+    ///     let mut layer = Vec::new();
+    ///     field(&mut layer, 1, b"points");
+    ///     field(&mut layer, 2, &feature);
+    ///
+    ///     let mut tile = Vec::new();
+    ///     field(&mut tile, 3, &layer);
+    ///     tile
+    /// }
+    ///
+    /// fn gzip(data: &[u8]) -> Vec<u8> {
+    ///     let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    ///     encoder.write_all(data).unwrap();
+    ///     encoder.finish().unwrap()
+    /// }
+    ///
+    /// // A `MoveTo` command to `(25, 25)`, with zigzag encoded parameters.
+    /// let point = tile(&[1 << 3 | 1, 50, 50]);
+    ///
+    /// let decoded = VectorTile::decode(&gzip(&point)).unwrap();
+    /// assert_eq!(decoded.layers[0].features[0].geometry, geo::Geometry::Point(geo::point!(x: 25.0, y: 25.0)));
+    ///
+    /// // Tiles compressed more than once are rejected, rather than decompressed over and over.
+    /// assert!(VectorTile::decode(&gzip(&gzip(&point))).is_err());
+    ///
+    /// // Coordinates moving past `i64::MAX` are rejected, rather than overflowing.
+    /// let overflowing = tile(&[2 << 3 | 1, u64::MAX - 1, 0, u64::MAX - 1, 0]);
+    /// assert!(matches!(VectorTile::decode(&overflowing), Err(snapr::Error::VectorTile { .. })));
+    /// ```
+    pub fn decode(data: &[u8]) -> Result<Self, Error> {
+        if data.starts_with(&GZIP_MAGIC) {
+            let mut decompressed = Vec::new();
+            GzDecoder::new(data).read_to_end(&mut decompressed)?;
+
+            if decompressed.starts_with(&GZIP_MAGIC) {
+                return Err(invalid("tile is compressed with gzip more than once"));
+            }
+
+            return Self::decode_uncompressed(&decompressed);
+        }
+
+        Self::decode_uncompressed(data)
+    }
+
+    fn decode_uncompressed(data: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(data);
+        let mut layers = Vec::new();
+
+        while let Some((field, wire_type)) = reader.field()? {
+            match (field, wire_type) {
+                (3, WireType::Bytes) => layers.push(decode_layer(reader.bytes()?)?),
+                _ => reader.skip(wire_type)?,
+            }
+        }
+
+        Ok(Self { layers })
+    }
+}
+
+fn decode_layer(data: &[u8]) -> Result<VectorLayer, Error> {
+    let mut reader = Reader::new(data);
+
+    let mut name = String::new();
+    let mut extent = 4096;
+    let mut encoded_features = Vec::new();
+    let mut keys = Vec::new();
+    let mut values = Vec::new();
+
+    while let Some((field, wire_type)) = reader.field()? {
+        match (field, wire_type) {
+            (1, WireType::Bytes) => name = reader.string()?,
+            (2, WireType::Bytes) => encoded_features.push(reader.bytes()?),
+            (3, WireType::Bytes) => keys.push(reader.string()?),
+            (4, WireType::Bytes) => values.push(decode_value(reader.bytes()?)?),
+            (5, WireType::Varint) => extent = reader.varint()? as u32,
+            _ => reader.skip(wire_type)?,
+        }
+    }
+
+    if extent == 0 {
+        return Err(invalid("layer extent is zero"));
+    }
+
+    let mut features = Vec::with_capacity(encoded_features.len());
+
+    for data in encoded_features {
+        if let Some(feature) = decode_feature(data, &keys, &values)? {
+            features.push(feature);
+        }
+    }
+
+    Ok(VectorLayer {
+        name,
+        extent,
+        features,
+    })
+}
+
+fn decode_feature(
+    data: &[u8],
+    keys: &[String],
+    values: &[PropertyValue],
+) -> Result<Option<VectorFeature>, Error> {
+    let mut reader = Reader::new(data);
+
+    let mut id = None;
+    let mut tags = Vec::new();
+    let mut geometry_type = 0;
+    let mut commands = Vec::new();
+
+    while let Some((field, wire_type)) = reader.field()? {
+        match (field, wire_type) {
+            (1, WireType::Varint) => id = Some(reader.varint()?),
+            (2, WireType::Bytes) => tags = reader.packed()?,
+            (3, WireType::Varint) => geometry_type = reader.varint()?,
+            (4, WireType::Bytes) => commands = reader.packed()?,
+            _ => reader.skip(wire_type)?,
+        }
+    }
+
+    let mut properties = HashMap::with_capacity(tags.len() / 2);
+
+    for tag in tags.chunks_exact(2) {
+        let key = keys.get(tag[0] as usize);
+        let value = values.get(tag[1] as usize);
+
+        match key.zip(value) {
+            Some((key, value)) => properties.insert(key.clone(), value.clone()),
+            None => return Err(invalid("feature tag is out of range")),
+        };
+    }
+
+    let geometry = decode_geometry(geometry_type, &commands)?;
+
+    Ok(geometry.map(|geometry| VectorFeature {
+        id,
+        geometry,
+        properties,
+    }))
+}
+
+fn decode_value(data: &[u8]) -> Result<PropertyValue, Error> {
+    let mut reader = Reader::new(data);
+    let mut value = PropertyValue::String(String::new());
+
+    while let Some((field, wire_type)) = reader.field()? {
+        value = match (field, wire_type) {
+            (1, WireType::Bytes) => PropertyValue::String(reader.string()?),
+            (2, WireType::Fixed32) => {
+                PropertyValue::Float(f32::from_le_bytes(reader.fixed()?) as f64)
+            }
+            (3, WireType::Fixed64) => PropertyValue::Float(f64::from_le_bytes(reader.fixed()?)),
+            (4, WireType::Varint) => PropertyValue::Int(reader.varint()? as i64),
+            (5, WireType::Varint) => PropertyValue::UInt(reader.varint()?),
+            (6, WireType::Varint) => PropertyValue::Int(zigzag(reader.varint()?)),
+            (7, WireType::Varint) => PropertyValue::Bool(reader.varint()? != 0),
+            _ => {
+                reader.skip(wire_type)?;
+                continue;
+            }
+        };
+    }
+
+    Ok(value)
+}
+
+/// Decodes the geometry `commands` of a feature of the given `geometry_type`, returning [`None`] for unknown geometry types or empty geometries.
+fn decode_geometry(geometry_type: u64, commands: &[u64]) -> Result<Option<geo::Geometry>, Error> {
+    const MOVE_TO: u64 = 1;
+    const LINE_TO: u64 = 2;
+    const CLOSE_PATH: u64 = 7;
+
+    let mut parts: Vec<Vec<geo::Coord>> = Vec::new();
+    let mut cursor = (0_i64, 0_i64);
+    let mut commands = commands.iter();
+
+    while let Some(&command) = commands.next() {
+        let (id, count) = (command & 0x7, command >> 3);
+
+        match id {
+            MOVE_TO | LINE_TO => {
+                for index in 0..count {
+                    let (Some(&dx), Some(&dy)) = (commands.next(), commands.next()) else {
+                        return Err(invalid("geometry command is missing parameters"));
+                    };
+
+                    let (Some(x), Some(y)) = (
+                        cursor.0.checked_add(zigzag(dx)),
+                        cursor.1.checked_add(zigzag(dy)),
+                    ) else {
+                        return Err(invalid("geometry coordinates overflow"));
+                    };
+
+                    cursor = (x, y);
+                    let coord = geo::coord! { x: cursor.0 as f64, y: cursor.1 as f64 };
+
+                    // Every point of a `MoveTo` command starts a new part, which only matters for multi-points.
+                    match (id, parts.last_mut()) {
+                        (LINE_TO, Some(part)) => part.push(coord),
+                        (LINE_TO, None) => return Err(invalid("geometry starts with `LineTo`")),
+                        _ if index == 0 || geometry_type == 1 => parts.push(vec![coord]),
+                        _ => return Err(invalid("geometry `MoveTo` has multiple points")),
+                    }
+                }
+            }
+
+            CLOSE_PATH => {
+                if let Some(part) = parts.last_mut() {
+                    if let Some(&first) = part.first() {
+                        part.push(first);
+                    }
+                }
+            }
+
+            _ => return Err(invalid("geometry command is unknown")),
+        }
+    }
+
+    let geometry = match geometry_type {
+        1 => {
+            let mut points = parts
+                .into_iter()
+                .flatten()
+                .map(geo::Point::from)
+                .collect::<Vec<_>>();
+
+            match points.len() {
+                0 => return Ok(None),
+                1 => geo::Geometry::Point(points.remove(0)),
+                _ => geo::Geometry::MultiPoint(geo::MultiPoint(points)),
+            }
+        }
+
+        2 => {
+            let mut lines = parts
+                .into_iter()
+                .map(geo::LineString::from)
+                .collect::<Vec<_>>();
+
+            match lines.len() {
+                0 => return Ok(None),
+                1 => geo::Geometry::LineString(lines.remove(0)),
+                _ => geo::Geometry::MultiLineString(geo::MultiLineString(lines)),
+            }
+        }
+
+        3 => {
+            let mut polygons: Vec<(geo::LineString, Vec<geo::LineString>)> = Vec::new();
+
+            // Exterior rings have a positive area in tile coordinates, and are followed by their interior rings.
+            for ring in parts.into_iter().map(geo::LineString::from) {
+                let area = signed_area(&ring);
+
+                match polygons.last_mut() {
+                    _ if area == 0.0 => continue,
+                    Some((_, interiors)) if area < 0.0 => interiors.push(ring),
+                    _ => polygons.push((ring, Vec::new())),
+                }
+            }
+
+            let mut polygons = polygons
+                .into_iter()
+                .map(|(exterior, interiors)| geo::Polygon::new(exterior, interiors))
+                .collect::<Vec<_>>();
+
+            match polygons.len() {
+                0 => return Ok(None),
+                1 => geo::Geometry::Polygon(polygons.remove(0)),
+                _ => geo::Geometry::MultiPolygon(geo::MultiPolygon(polygons)),
+            }
+        }
+
+        _ => return Ok(None),
+    };
+
+    Ok(Some(geometry))
+}
+
+/// Returns the signed area of the given `ring` with the shoelace formula, positive for clockwise rings when the `y` axis points down.
+fn signed_area(ring: &geo::LineString) -> f64 {
+    ring.lines()
+        .map(|line| line.start.x * line.end.y - line.end.x * line.start.y)
+        .sum::<f64>()
+        / 2.0
+}
+
+fn zigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::VectorTile {
+        reason: reason.to_string(),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WireType {
+    Varint,
+    Fixed64,
+    Bytes,
+    Fixed32,
+}
+
+/// Minimal reader of the [protocol buffers](https://protobuf.dev/programming-guides/encoding) wire format, covering what vector tiles use.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Reads the key of the next field, returning [`None`] once every field has been read.
+    fn field(&mut self) -> Result<Option<(u64, WireType)>, Error> {
+        if self.data.is_empty() {
+            return Ok(None);
+        }
+
+        let key = self.varint()?;
+
+        let wire_type = match key & 0x7 {
+            0 => WireType::Varint,
+            1 => WireType::Fixed64,
+            2 => WireType::Bytes,
+            5 => WireType::Fixed32,
+            _ => return Err(invalid("protobuf wire type is unsupported")),
+        };
+
+        Ok(Some((key >> 3, wire_type)))
+    }
+
+    fn varint(&mut self) -> Result<u64, Error> {
+        let mut value = 0;
+
+        for shift in (0..64).step_by(7) {
+            let Some((&byte, rest)) = self.data.split_first() else {
+                return Err(invalid("protobuf varint is truncated"));
+            };
+
+            self.data = rest;
+            value |= ((byte & 0x7f) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(invalid("protobuf varint is too long"))
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], Error> {
+        if length > self.data.len() {
+            return Err(invalid("protobuf field is truncated"));
+        }
+
+        let (taken, rest) = self.data.split_at(length);
+        self.data = rest;
+
+        Ok(taken)
+    }
+
+    fn fixed<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let bytes = self.take(N)?;
+        Ok(bytes
+            .try_into()
+            .expect("taken bytes should be exactly `N` bytes long"))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], Error> {
+        let length = self.varint()?;
+        self.take(usize::try_from(length).unwrap_or(usize::MAX))
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        let bytes = self.bytes()?;
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    /// Reads a packed field of varints.
+    fn packed(&mut self) -> Result<Vec<u64>, Error> {
+        let mut reader = Reader::new(self.bytes()?);
+        let mut values = Vec::new();
+
+        while !reader.data.is_empty() {
+            values.push(reader.varint()?);
+        }
+
+        Ok(values)
+    }
+
+    fn skip(&mut self, wire_type: WireType) -> Result<(), Error> {
+        match wire_type {
+            WireType::Varint => self.varint().map(|_| ()),
+            WireType::Fixed64 => self.take(8).map(|_| ()),
+            WireType::Bytes => self.bytes().map(|_| ()),
+            WireType::Fixed32 => self.take(4).map(|_| ()),
+        }
+    }
+}
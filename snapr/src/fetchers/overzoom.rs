@@ -0,0 +1,373 @@
+//! Contains a fallback rendering tiles from their ancestors at lower zoom levels, that can wrap any [`TileFetcher`].
+//! See [`Overzoom`] for more details.
+
+use std::collections::{HashMap, HashSet};
+
+use image::{imageops::FilterType, DynamicImage, GenericImageView};
+
+use crate::Error;
+
+use super::{BatchTileFetcher, IndividualTileFetcher, TileFetcher};
+
+#[cfg(feature = "tokio")]
+use std::sync::Arc;
+
+#[cfg(feature = "tokio")]
+use super::{AsyncBatchTileFetcher, AsyncIndividualTileFetcher, AsyncTileFetcher};
+
+/// Fallback rendering tiles that are unavailable at the requested zoom level from their nearest available ancestor tile.
+///
+/// The quadrant of the ancestor covering the requested tile is cropped, and upscaled back to the size of the ancestor tile.
+/// Ancestors are requested when:
+/// - The requested zoom level exceeds the configured [`max_zoom`](Self::with_max_zoom) of the source, in which case the ancestor at the `max_zoom` is requested directly.
+/// - The wrapped [`TileFetcher`] reports the requested tile as [`Error::TileNotFound`], or leaves it out of a batch,
+///   in which case ancestors are requested one zoom level at a time, up to the configured number of [`fallback_levels`](Self::with_fallback_levels).
+///
+/// ## Example
+///
+/// ```rust
+/// use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+/// use snapr::{fetchers::{overzoom::Overzoom, IndividualTileFetcher}, Error, TileFetcher};
+///
+/// // Only serves zoom level `0`, whose left half is red and right half is blue.
+/// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+///     if zoom > 0 {
+///         return Err(Error::TileNotFound { x, y, zoom });
+///     }
+///
+///     Ok(DynamicImage::from(RgbaImage::from_fn(256, 256, |x, _| match x < 128 {
+///         true => Rgba([255, 0, 0, 255]),
+///         false => Rgba([0, 0, 255, 255]),
+///     })))
+/// }
+///
+/// let TileFetcher::Individual(tile_fetcher) = Overzoom::new().wrap(TileFetcher::individual(tile_fetcher)) else {
+///     unreachable!()
+/// };
+///
+/// let tile = tile_fetcher.fetch_tile(3, 0, 2).unwrap();
+///
+/// assert_eq!(tile.dimensions(), (256, 256));
+/// assert_eq!(tile.get_pixel(128, 128), Rgba([0, 0, 255, 255]));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Overzoom {
+    max_zoom: Option<u8>,
+    fallback_levels: u8,
+}
+
+impl Default for Overzoom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Overzoom {
+    /// Constructs a new [`Overzoom`] falling back to ancestors up to `4` zoom levels above missing tiles, without a `max_zoom`.
+    pub fn new() -> Self {
+        Self {
+            max_zoom: None,
+            fallback_levels: 4,
+        }
+    }
+
+    /// Configures the highest zoom level available from the source, tiles requested above it are rendered from their ancestor at `max_zoom`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+    /// use snapr::{fetchers::{overzoom::Overzoom, IndividualTileFetcher}, TileFetcher};
+    ///
+    /// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     Ok(RgbaImage::from_pixel(256, 256, Rgba([0, 0, 255, 255])).into())
+    /// }
+    ///
+    /// let overzoom = Overzoom::new().with_max_zoom(0);
+    ///
+    /// let TileFetcher::Individual(tile_fetcher) = overzoom.wrap(TileFetcher::individual(tile_fetcher)) else {
+    ///     unreachable!()
+    /// };
+    ///
+    /// // Tiles far past the `max_zoom` are still rendered from the single tile at zoom level `0`.
+    /// let tile = tile_fetcher.fetch_tile(5, 7, 40).unwrap();
+    /// assert_eq!(tile.get_pixel(0, 0), Rgba([0, 0, 255, 255]));
+    /// ```
+    pub fn with_max_zoom(self, max_zoom: u8) -> Self {
+        Self {
+            max_zoom: Some(max_zoom),
+            ..self
+        }
+    }
+
+    /// Configures how many zoom levels above the first requested zoom level ancestors of missing tiles are looked for.
+    /// Setting `fallback_levels` to `0` disables the automatic fallback, only leaving the [`max_zoom`](Self::with_max_zoom) in effect.
+    pub fn with_fallback_levels(self, fallback_levels: u8) -> Self {
+        Self {
+            fallback_levels,
+            ..self
+        }
+    }
+
+    /// Wraps the given `tile_fetcher`, rendering tiles it can't provide from their ancestors.
+    /// The returned [`TileFetcher`] is of the same variant as the given `tile_fetcher`.
+    pub fn wrap<'a>(&self, tile_fetcher: TileFetcher<'a>) -> TileFetcher<'a> {
+        match tile_fetcher {
+            TileFetcher::Individual(tile_fetcher) => TileFetcher::individual(OverzoomIndividual {
+                tile_fetcher,
+                overzoom: *self,
+            }),
+
+            TileFetcher::Batch(tile_fetcher) => TileFetcher::batch(OverzoomBatch {
+                tile_fetcher,
+                overzoom: *self,
+            }),
+        }
+    }
+
+    /// Wraps the given asynchronous `tile_fetcher`, rendering tiles it can't provide from their ancestors.
+    /// The returned [`AsyncTileFetcher`] is of the same variant as the given `tile_fetcher`.
+    #[cfg(feature = "tokio")]
+    pub fn wrap_async<'a>(&self, tile_fetcher: AsyncTileFetcher<'a>) -> AsyncTileFetcher<'a> {
+        match tile_fetcher {
+            AsyncTileFetcher::Individual(tile_fetcher) => {
+                AsyncTileFetcher::individual(OverzoomAsyncIndividual {
+                    tile_fetcher,
+                    overzoom: *self,
+                })
+            }
+
+            AsyncTileFetcher::Batch(tile_fetcher) => AsyncTileFetcher::batch(OverzoomAsyncBatch {
+                tile_fetcher,
+                overzoom: *self,
+            }),
+        }
+    }
+
+    /// Returns the zoom levels to request a tile at the given `zoom` level from, in order.
+    fn levels(&self, zoom: u8) -> impl Iterator<Item = u8> {
+        let first = self.max_zoom.map_or(zoom, |max_zoom| zoom.min(max_zoom));
+        let last = first.saturating_sub(self.fallback_levels);
+
+        (last..=first).rev()
+    }
+}
+
+/// Returns the position of the ancestor of the tile at the given `x`, `y`, and `zoom` values at the `ancestor_zoom` level.
+fn ancestor(x: i32, y: i32, zoom: u8, ancestor_zoom: u8) -> (i32, i32) {
+    // Shifting an `i32` by `31` bits already leaves only its sign, so deeper ancestors share the same position.
+    let depth = ((zoom - ancestor_zoom) as u32).min(i32::BITS - 1);
+    (x >> depth, y >> depth)
+}
+
+/// Crops the quadrant of the `ancestor` tile covering the tile at the given `x`, `y`, and `zoom` values,
+/// and upscales it back to the size of the `ancestor`.
+fn crop_descendant(
+    ancestor: &DynamicImage,
+    x: i32,
+    y: i32,
+    zoom: u8,
+    ancestor_zoom: u8,
+) -> DynamicImage {
+    // Quadrants past `62` levels deep are narrower than a pixel for every `i32` position, so the depth is clamped to keep the math within `i64s`.
+    let depth = ((zoom - ancestor_zoom) as u32).min(62);
+
+    let (width, height) = ancestor.dimensions();
+
+    if depth == 0 || width == 0 || height == 0 {
+        return ancestor.clone();
+    }
+
+    let divisions = (1_u64 << depth) as f64;
+    let mask = (1_i64 << depth) - 1;
+
+    // Bounds of the quadrant, spanning at least a single pixel once the ancestor can't be divided any further.
+    let bounds = |offset: i64, size: u32| {
+        let start = ((offset as f64 * size as f64 / divisions).floor() as u32).min(size - 1);
+        let end = ((offset + 1) as f64 * size as f64 / divisions).floor() as u32;
+        (start, end.clamp(start + 1, size) - start)
+    };
+
+    let (crop_x, crop_width) = bounds(x as i64 & mask, width);
+    let (crop_y, crop_height) = bounds(y as i64 & mask, height);
+
+    ancestor
+        .crop_imm(crop_x, crop_y, crop_width, crop_height)
+        .resize_exact(width, height, FilterType::Triangle)
+}
+
+/// Moves the tiles in `missing` that have an ancestor in `ancestors` into `tiles`.
+fn crop_from_ancestors(
+    missing: &mut Vec<(i32, i32)>,
+    tiles: &mut Vec<(i32, i32, DynamicImage)>,
+    ancestors: &HashMap<(i32, i32), DynamicImage>,
+    zoom: u8,
+    ancestor_zoom: u8,
+) {
+    missing.retain(
+        |&(x, y)| match ancestors.get(&ancestor(x, y, zoom, ancestor_zoom)) {
+            Some(tile) => {
+                tiles.push((x, y, crop_descendant(tile, x, y, zoom, ancestor_zoom)));
+                false
+            }
+
+            None => true,
+        },
+    );
+}
+
+/// Returns the distinct ancestors of the tiles in `missing` at the `ancestor_zoom` level.
+fn missing_ancestors(missing: &[(i32, i32)], zoom: u8, ancestor_zoom: u8) -> Vec<(i32, i32)> {
+    let mut seen = HashSet::new();
+
+    missing
+        .iter()
+        .map(|&(x, y)| ancestor(x, y, zoom, ancestor_zoom))
+        .filter(|position| seen.insert(*position))
+        .collect()
+}
+
+/// [`IndividualTileFetcher`] returned by [`Overzoom::wrap`].
+struct OverzoomIndividual<'a> {
+    tile_fetcher: Box<dyn IndividualTileFetcher + 'a>,
+    overzoom: Overzoom,
+}
+
+impl<'a> IndividualTileFetcher for OverzoomIndividual<'a> {
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        for ancestor_zoom in self.overzoom.levels(zoom) {
+            let (ancestor_x, ancestor_y) = ancestor(x, y, zoom, ancestor_zoom);
+
+            match self
+                .tile_fetcher
+                .fetch_tile(ancestor_x, ancestor_y, ancestor_zoom)
+            {
+                Ok(tile) => return Ok(crop_descendant(&tile, x, y, zoom, ancestor_zoom)),
+                Err(Error::TileNotFound { .. }) => continue,
+                Err(error) => return Err(error),
+            }
+        }
+
+        Err(Error::TileNotFound { x, y, zoom })
+    }
+}
+
+/// [`BatchTileFetcher`] returned by [`Overzoom::wrap`].
+///
+/// Tiles without any available ancestor are left out of the returned tiles.
+struct OverzoomBatch<'a> {
+    tile_fetcher: Box<dyn BatchTileFetcher + 'a>,
+    overzoom: Overzoom,
+}
+
+impl<'a> BatchTileFetcher for OverzoomBatch<'a> {
+    fn fetch_tiles(
+        &self,
+        coordinate_matrix: &[(i32, i32)],
+        zoom: u8,
+    ) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+        let mut tiles = Vec::with_capacity(coordinate_matrix.len());
+        let mut missing = coordinate_matrix.to_vec();
+
+        for ancestor_zoom in self.overzoom.levels(zoom) {
+            if missing.is_empty() {
+                break;
+            }
+
+            let ancestors = missing_ancestors(&missing, zoom, ancestor_zoom);
+
+            let ancestors = match self.tile_fetcher.fetch_tiles(&ancestors, ancestor_zoom) {
+                Ok(ancestors) => ancestors,
+                Err(Error::TileNotFound { .. }) => continue,
+                Err(error) => return Err(error),
+            };
+
+            let ancestors = ancestors
+                .into_iter()
+                .map(|(x, y, tile)| ((x, y), tile))
+                .collect();
+
+            crop_from_ancestors(&mut missing, &mut tiles, &ancestors, zoom, ancestor_zoom);
+        }
+
+        Ok(tiles)
+    }
+}
+
+/// [`AsyncIndividualTileFetcher`] returned by [`Overzoom::wrap_async`].
+#[cfg(feature = "tokio")]
+struct OverzoomAsyncIndividual {
+    tile_fetcher: Arc<dyn AsyncIndividualTileFetcher>,
+    overzoom: Overzoom,
+}
+
+#[cfg(feature = "tokio")]
+#[async_trait::async_trait]
+impl AsyncIndividualTileFetcher for OverzoomAsyncIndividual {
+    async fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        for ancestor_zoom in self.overzoom.levels(zoom) {
+            let (ancestor_x, ancestor_y) = ancestor(x, y, zoom, ancestor_zoom);
+
+            match self
+                .tile_fetcher
+                .fetch_tile(ancestor_x, ancestor_y, ancestor_zoom)
+                .await
+            {
+                Ok(tile) => return Ok(crop_descendant(&tile, x, y, zoom, ancestor_zoom)),
+                Err(Error::TileNotFound { .. }) => continue,
+                Err(error) => return Err(error),
+            }
+        }
+
+        Err(Error::TileNotFound { x, y, zoom })
+    }
+}
+
+/// [`AsyncBatchTileFetcher`] returned by [`Overzoom::wrap_async`].
+///
+/// Tiles without any available ancestor are left out of the returned tiles.
+#[cfg(feature = "tokio")]
+struct OverzoomAsyncBatch<'a> {
+    tile_fetcher: Box<dyn AsyncBatchTileFetcher + 'a>,
+    overzoom: Overzoom,
+}
+
+#[cfg(feature = "tokio")]
+#[async_trait::async_trait]
+impl<'a> AsyncBatchTileFetcher for OverzoomAsyncBatch<'a> {
+    async fn fetch_tiles(
+        &self,
+        coordinate_matrix: Vec<(i32, i32)>,
+        zoom: u8,
+    ) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+        let mut tiles = Vec::with_capacity(coordinate_matrix.len());
+        let mut missing = coordinate_matrix;
+
+        for ancestor_zoom in self.overzoom.levels(zoom) {
+            if missing.is_empty() {
+                break;
+            }
+
+            let ancestors = missing_ancestors(&missing, zoom, ancestor_zoom);
+
+            let ancestors = match self
+                .tile_fetcher
+                .fetch_tiles(ancestors, ancestor_zoom)
+                .await
+            {
+                Ok(ancestors) => ancestors,
+                Err(Error::TileNotFound { .. }) => continue,
+                Err(error) => return Err(error),
+            };
+
+            let ancestors = ancestors
+                .into_iter()
+                .map(|(x, y, tile)| ((x, y), tile))
+                .collect();
+
+            crop_from_ancestors(&mut missing, &mut tiles, &ancestors, zoom, ancestor_zoom);
+        }
+
+        Ok(tiles)
+    }
+}
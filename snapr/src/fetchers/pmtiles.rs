@@ -0,0 +1,615 @@
+//! Contains a tile fetcher reading raster tiles from [PMTiles](https://github.com/protomaps/PMTiles) v3 archives.
+//! See [`PmTiles`] for more details.
+
+use std::{
+    fmt,
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    num::NonZeroUsize,
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use flate2::read::GzDecoder;
+use image::DynamicImage;
+use lru::LruCache;
+
+use crate::Error;
+
+use super::{IndividualTileFetcher, TileCoord};
+
+/// Maximum amount of directories traversed when looking up a tile, as defined by the specification.
+const MAX_DIRECTORY_DEPTH: usize = 4;
+
+/// Greatest zoom level whose tiles can be given a tile ID, see [`tile_id`].
+const MAX_TILE_ZOOM: u8 = 31;
+
+/// Amount of leaf directories kept in memory after being read.
+const LEAF_DIRECTORY_CAPACITY: usize = 64;
+
+/// Tile fetcher reading raster tiles from a [PMTiles](https://github.com/protomaps/PMTiles) v3 archive.
+///
+/// Archives can be read from local files with [`open`](Self::open), or from any [`Read`] + [`Seek`] source with [`from_reader`](Self::from_reader).
+/// Tiles missing from the archive are reported as [`Error::TileNotFound`].
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use snapr::{fetchers::pmtiles::PmTiles, SnaprBuilder, TileFetcher};
+///
+/// let pmtiles = PmTiles::open("basemap.pmtiles").unwrap();
+/// println!("{:?}", pmtiles.header().tile_type);
+///
+/// let snapr = SnaprBuilder::new()
+///     .with_tile_fetcher(TileFetcher::individual(pmtiles))
+///     .build();
+/// ```
+pub struct PmTiles<R> {
+    reader: Mutex<R>,
+
+    /// Length of the archive in bytes, bounding every read from the `reader`.
+    length: u64,
+
+    header: PmTilesHeader,
+    root_directory: Vec<Entry>,
+    leaf_directories: Mutex<LruCache<(u64, u64), Directory>>,
+}
+
+/// Parsed entries of a [`PmTiles`] directory, shared between lookups once cached.
+type Directory = Arc<Vec<Entry>>;
+
+/// Compression used by the directories, metadata, or tiles of a [`PmTiles`] archive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Compression {
+    Unknown,
+    None,
+    Gzip,
+    Brotli,
+    Zstd,
+}
+
+/// Type of the tiles contained by a [`PmTiles`] archive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TileType {
+    Unknown,
+    Mvt,
+    Png,
+    Jpeg,
+    Webp,
+    Avif,
+}
+
+/// Header of a [`PmTiles`] archive, describing its layout and contents.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PmTilesHeader {
+    pub root_directory_offset: u64,
+    pub root_directory_length: u64,
+    pub metadata_offset: u64,
+    pub metadata_length: u64,
+    pub leaf_directories_offset: u64,
+    pub leaf_directories_length: u64,
+    pub tile_data_offset: u64,
+    pub tile_data_length: u64,
+    pub addressed_tiles_count: u64,
+    pub tile_entries_count: u64,
+    pub tile_contents_count: u64,
+    pub clustered: bool,
+    pub internal_compression: Compression,
+    pub tile_compression: Compression,
+    pub tile_type: TileType,
+    pub min_zoom: u8,
+    pub max_zoom: u8,
+
+    /// Bounds of the archive, where `x` is the longitude and `y` is the latitude.
+    pub bounds: geo::Rect,
+    pub center_zoom: u8,
+
+    /// Center of the archive, where `x` is the longitude and `y` is the latitude.
+    pub center: geo::Point,
+}
+
+/// Entry of a [`PmTiles`] directory, pointing at either a run of tiles or a leaf directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Entry {
+    tile_id: u64,
+    offset: u64,
+    length: u64,
+    run_length: u64,
+}
+
+impl PmTiles<BufReader<File>> {
+    /// Opens the [PMTiles](https://github.com/protomaps/PMTiles) archive at the given `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = File::open(path)?;
+        Self::from_reader(BufReader::new(file))
+    }
+}
+
+impl<R: Read + Seek> PmTiles<R> {
+    /// Constructs a new [`PmTiles`] from a `reader` of a [PMTiles](https://github.com/protomaps/PMTiles) archive, reading its header and root directory.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::io::{Cursor, Write};
+    ///
+    /// use flate2::{write::GzEncoder, Compression};
+    /// use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+    /// use snapr::fetchers::{pmtiles::{tile_id, PmTiles}, IndividualTileFetcher, TileCoord};
+    ///
+    /// fn varint(bytes: &mut Vec<u8>, mut value: u64) {
+    ///     while value >= 0x80 {
+    ///         bytes.push(value as u8 | 0x80);
+    ///         value >>= 7;
+    ///     }
+    ///
+    ///     bytes.push(value as u8);
+    /// }
+    ///
+    /// // Gzip-compressed directory of a single entry, `(tile_id, run_length, length, offset)`.
+    /// fn directory(tile_id: u64, run_length: u64, length: u64) -> Vec<u8> {
+    ///     let mut bytes = Vec::new();
+    ///
+    ///     for value in [1, tile_id, run_length, length, 1] {
+    ///         varint(&mut bytes, value);
+    ///     }
+    ///
+    ///     let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    ///     encoder.write_all(&bytes).unwrap();
+    ///     encoder.finish().unwrap()
+    /// }
+    ///
+    /// let mut tile = Cursor::new(Vec::new());
+    /// let image = RgbaImage::from_pixel(256, 256, Rgba([0, 0, 255, 255]));
+    /// DynamicImage::from(image).write_to(&mut tile, ImageFormat::Png).unwrap();
+    /// let tile = tile.into_inner();
+    ///
+    /// // The root directory points at a leaf directory, which points at the tile.
+    /// let coord = TileCoord::new(1, 0, 1);
+    /// let leaf = directory(tile_id(coord).unwrap(), 1, tile.len() as u64);
+    /// let root = directory(0, 0, leaf.len() as u64);
+    ///
+    /// let sections = [(127, root.len()), (127 + root.len(), leaf.len()), (127 + root.len() + leaf.len(), tile.len())];
+    ///
+    /// let mut archive = b"PMTiles\x03".to_vec();
+    /// for (offset, length) in [sections[0], (0, 0), sections[1], sections[2]] {
+    ///     archive.extend((offset as u64).to_le_bytes());
+    ///     archive.extend((length as u64).to_le_bytes());
+    /// }
+    ///
+    /// archive.resize(96, 0);
+    /// archive.extend([0, 2, 1, 2, 0, 1]); // Unclustered, gzip directories, uncompressed PNG tiles, zoom levels `0` to `1`.
+    /// archive.resize(127, 0);
+    /// archive.extend(root.iter().chain(&leaf).chain(&tile));
+    ///
+    /// let pmtiles = PmTiles::from_reader(Cursor::new(archive.clone())).unwrap();
+    /// let fetched = pmtiles.fetch_tile(coord.x, coord.y, coord.z).unwrap();
+    ///
+    /// assert_eq!(fetched.to_rgba8().get_pixel(0, 0), &Rgba([0, 0, 255, 255]));
+    /// assert!(pmtiles.tile_data(0, 0, 1).unwrap().is_none());
+    ///
+    /// // Offsets overflowing once added together are rejected, rather than wrapping around.
+    /// let mut overflowing = archive.clone();
+    /// overflowing[56..64].copy_from_slice(&u64::MAX.to_le_bytes());
+    ///
+    /// let pmtiles = PmTiles::from_reader(Cursor::new(overflowing)).unwrap();
+    /// assert!(matches!(pmtiles.tile_data(coord.x, coord.y, coord.z), Err(snapr::Error::Archive { .. })));
+    ///
+    /// // Sections claiming to extend past the end of the archive are rejected before being read.
+    /// archive[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+    ///
+    /// assert!(matches!(PmTiles::from_reader(Cursor::new(archive)), Err(snapr::Error::Archive { .. })));
+    /// ```
+    pub fn from_reader(mut reader: R) -> Result<Self, Error> {
+        let length = reader.seek(SeekFrom::End(0))?;
+        let header =
+            PmTilesHeader::parse(&read_at(&mut reader, length, 0, PmTilesHeader::LENGTH)?)?;
+
+        let root_directory = read_at(
+            &mut reader,
+            length,
+            header.root_directory_offset,
+            header.root_directory_length,
+        )?;
+
+        let root_directory =
+            parse_directory(&decompress(root_directory, header.internal_compression)?)?;
+
+        let capacity = NonZeroUsize::new(LEAF_DIRECTORY_CAPACITY).expect("capacity is non-zero");
+
+        Ok(Self {
+            reader: Mutex::new(reader),
+            length,
+            header,
+            root_directory,
+            leaf_directories: Mutex::new(LruCache::new(capacity)),
+        })
+    }
+
+    /// Returns the [`PmTilesHeader`] read when the archive was opened.
+    pub fn header(&self) -> &PmTilesHeader {
+        &self.header
+    }
+
+    /// Reads and decompresses the JSON metadata of the archive.
+    pub fn metadata(&self) -> Result<String, Error> {
+        let metadata = self.read(self.header.metadata_offset, self.header.metadata_length)?;
+        let metadata = decompress(metadata, self.header.internal_compression)?;
+
+        String::from_utf8(metadata).map_err(|_| Error::Archive {
+            reason: String::from("metadata is not valid UTF-8"),
+        })
+    }
+
+    /// Returns the decompressed data of the tile at the given XYZ `x`, `y`, and `zoom` values, or [`None`] if the archive doesn't contain it.
+    pub fn tile_data(&self, x: i32, y: i32, zoom: u8) -> Result<Option<Vec<u8>>, Error> {
+        if zoom < self.header.min_zoom || zoom > self.header.max_zoom {
+            return Ok(None);
+        }
+
+        let Some(tile_id) = tile_id(TileCoord::new(x, y, zoom)) else {
+            return Ok(None);
+        };
+
+        let mut directory = None;
+
+        for _ in 0..MAX_DIRECTORY_DEPTH {
+            let entries = directory.as_deref().unwrap_or(&self.root_directory);
+
+            let Some(entry) = find_entry(entries, tile_id) else {
+                return Ok(None);
+            };
+
+            if entry.run_length > 0 {
+                let offset = checked_offset(self.header.tile_data_offset, entry.offset)?;
+                let tile_data = self.read(offset, entry.length)?;
+                return decompress(tile_data, self.header.tile_compression).map(Some);
+            }
+
+            directory = Some(self.leaf_directory(entry)?);
+        }
+
+        Err(Error::Archive {
+            reason: format!("directories are nested deeper than {MAX_DIRECTORY_DEPTH} levels"),
+        })
+    }
+
+    /// Returns the leaf directory pointed at by the given `entry`, reading it from the archive if it isn't cached.
+    fn leaf_directory(&self, entry: Entry) -> Result<Directory, Error> {
+        let key = (entry.offset, entry.length);
+
+        if let Some(directory) = lock(&self.leaf_directories).get(&key) {
+            return Ok(directory.clone());
+        }
+
+        let offset = checked_offset(self.header.leaf_directories_offset, entry.offset)?;
+        let directory = self.read(offset, entry.length)?;
+        let directory = Arc::new(parse_directory(&decompress(
+            directory,
+            self.header.internal_compression,
+        )?)?);
+
+        lock(&self.leaf_directories).put(key, directory.clone());
+        Ok(directory)
+    }
+
+    fn read(&self, offset: u64, length: u64) -> Result<Vec<u8>, Error> {
+        read_at(&mut *lock(&self.reader), self.length, offset, length)
+    }
+}
+
+impl<R: Read + Seek + Send> IndividualTileFetcher for PmTiles<R> {
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        match self.tile_data(x, y, zoom)? {
+            Some(tile_data) => Ok(image::load_from_memory(&tile_data)?),
+            None => Err(Error::TileNotFound { x, y, zoom }),
+        }
+    }
+}
+
+impl<R> fmt::Debug for PmTiles<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PmTiles")
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+impl PmTilesHeader {
+    /// Length of the header in bytes.
+    const LENGTH: u64 = 127;
+
+    fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < Self::LENGTH as usize || &bytes[0..7] != b"PMTiles" {
+            return Err(Error::Archive {
+                reason: String::from("missing `PMTiles` magic number"),
+            });
+        }
+
+        if bytes[7] != 3 {
+            return Err(Error::Archive {
+                reason: format!("unsupported specification version `{}`", bytes[7]),
+            });
+        }
+
+        let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+        let degrees_at =
+            |at: usize| i32::from_le_bytes(bytes[at..at + 4].try_into().unwrap()) as f64 / 1e7;
+
+        Ok(Self {
+            root_directory_offset: u64_at(8),
+            root_directory_length: u64_at(16),
+            metadata_offset: u64_at(24),
+            metadata_length: u64_at(32),
+            leaf_directories_offset: u64_at(40),
+            leaf_directories_length: u64_at(48),
+            tile_data_offset: u64_at(56),
+            tile_data_length: u64_at(64),
+            addressed_tiles_count: u64_at(72),
+            tile_entries_count: u64_at(80),
+            tile_contents_count: u64_at(88),
+            clustered: bytes[96] == 1,
+            internal_compression: Compression::from(bytes[97]),
+            tile_compression: Compression::from(bytes[98]),
+            tile_type: TileType::from(bytes[99]),
+            min_zoom: bytes[100],
+            max_zoom: bytes[101],
+            bounds: geo::Rect::new(
+                geo::coord! { x: degrees_at(102), y: degrees_at(106) },
+                geo::coord! { x: degrees_at(110), y: degrees_at(114) },
+            ),
+            center_zoom: bytes[118],
+            center: geo::point!(x: degrees_at(119), y: degrees_at(123)),
+        })
+    }
+}
+
+impl From<u8> for Compression {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::None,
+            2 => Self::Gzip,
+            3 => Self::Brotli,
+            4 => Self::Zstd,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl From<u8> for TileType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::Mvt,
+            2 => Self::Png,
+            3 => Self::Jpeg,
+            4 => Self::Webp,
+            5 => Self::Avif,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Converts a [`TileCoord`] to its [PMTiles](https://github.com/protomaps/PMTiles) tile ID, the position of the tile along a Hilbert curve covering every zoom level.
+/// Returns [`None`] if the `coord` lies outside of the tile matrix.
+///
+/// ## Example
+///
+/// ```rust
+/// use snapr::fetchers::{pmtiles::{tile_coord, tile_id}, TileCoord};
+///
+/// let coord = TileCoord::new(3, 1, 2);
+///
+/// assert_eq!(tile_id(coord), Some(17));
+/// assert_eq!(tile_coord(17), Some(coord));
+///
+/// // Tile IDs are only defined up to zoom level `31`.
+/// assert_eq!(tile_id(TileCoord::new(0, 0, 64)), None);
+/// assert_eq!(tile_coord(u64::MAX), None);
+/// ```
+pub fn tile_id(coord: TileCoord) -> Option<u64> {
+    if coord.z > MAX_TILE_ZOOM {
+        return None;
+    }
+
+    let size = 1_i64 << coord.z;
+
+    if !(0..size).contains(&(coord.x as i64)) || !(0..size).contains(&(coord.y as i64)) {
+        return None;
+    }
+
+    // Amount of tiles in every zoom level below the one of the `coord`.
+    let base = ((1_u64 << (2 * coord.z as u64)) - 1) / 3;
+
+    let size = size as u64;
+    let (mut x, mut y) = (coord.x as u64, coord.y as u64);
+    let mut position = 0;
+    let mut s = size / 2;
+
+    while s > 0 {
+        let rx = ((x & s) > 0) as u64;
+        let ry = ((y & s) > 0) as u64;
+
+        position += s * s * ((3 * rx) ^ ry);
+        (x, y) = rotate(size, x, y, rx, ry);
+        s /= 2;
+    }
+
+    Some(base + position)
+}
+
+/// Inverse of [`tile_id`], converts a [PMTiles](https://github.com/protomaps/PMTiles) tile ID back into a [`TileCoord`].
+/// Returns [`None`] if the `tile_id` lies past the last zoom level [`tile_id`] supports.
+pub fn tile_coord(tile_id: u64) -> Option<TileCoord> {
+    let mut base = 0;
+    let mut z = 0;
+
+    // Tiles of every zoom level up to `MAX_TILE_ZOOM` add up to less than `u64::MAX`, so `base` can't overflow.
+    while base + (1_u64 << (2 * z)) <= tile_id {
+        if z == MAX_TILE_ZOOM {
+            return None;
+        }
+
+        base += 1_u64 << (2 * z);
+        z += 1;
+    }
+
+    let mut position = tile_id - base;
+    let (mut x, mut y) = (0, 0);
+    let mut s = 1;
+
+    while s < (1_u64 << z) {
+        let rx = 1 & (position / 2);
+        let ry = 1 & (position ^ rx);
+
+        (x, y) = rotate(s, x, y, rx, ry);
+        x += s * rx;
+        y += s * ry;
+        position /= 4;
+        s *= 2;
+    }
+
+    Some(TileCoord::new(x as i32, y as i32, z))
+}
+
+/// Rotates a quadrant of the Hilbert curve, see [`tile_id`].
+fn rotate(n: u64, x: u64, y: u64, rx: u64, ry: u64) -> (u64, u64) {
+    match (rx, ry) {
+        (1, 0) => (n - 1 - y, n - 1 - x),
+        (_, 0) => (y, x),
+        _ => (x, y),
+    }
+}
+
+/// Finds the entry containing the `tile_id`, being the entry with the greatest tile ID less than or equal to it.
+fn find_entry(entries: &[Entry], tile_id: u64) -> Option<Entry> {
+    let index = entries.partition_point(|entry| entry.tile_id <= tile_id);
+    let entry = *entries.get(index.checked_sub(1)?)?;
+
+    // Leaf directory entries cover every tile ID until the next entry.
+    match entry.run_length {
+        0 => Some(entry),
+        run_length if tile_id - entry.tile_id < run_length => Some(entry),
+        _ => None,
+    }
+}
+
+/// Parses a decompressed directory, made up of columns of varints for each field of its entries.
+fn parse_directory(mut bytes: &[u8]) -> Result<Vec<Entry>, Error> {
+    let count = read_varint(&mut bytes)? as usize;
+
+    // Every entry takes up at least four bytes, guarding against allocating for a corrupted count.
+    if count > bytes.len() / 4 {
+        return Err(Error::Archive {
+            reason: String::from("directory is truncated"),
+        });
+    }
+
+    let mut entries = vec![
+        Entry {
+            tile_id: 0,
+            offset: 0,
+            length: 0,
+            run_length: 0,
+        };
+        count
+    ];
+
+    let mut tile_id = 0;
+
+    for entry in entries.iter_mut() {
+        tile_id = checked_offset(tile_id, read_varint(&mut bytes)?)?;
+        entry.tile_id = tile_id;
+    }
+
+    for entry in entries.iter_mut() {
+        entry.run_length = read_varint(&mut bytes)?;
+    }
+
+    for entry in entries.iter_mut() {
+        entry.length = read_varint(&mut bytes)?;
+    }
+
+    for index in 0..count {
+        // An offset of `0` marks an entry directly following the previous one.
+        entries[index].offset = match (read_varint(&mut bytes)?, index) {
+            (0, 1..) => checked_offset(entries[index - 1].offset, entries[index - 1].length)?,
+            (offset, _) => offset.saturating_sub(1),
+        };
+    }
+
+    Ok(entries)
+}
+
+/// Adds the `offset` to the `base` read from the archive, failing rather than overflowing on corrupted values.
+fn checked_offset(base: u64, offset: u64) -> Result<u64, Error> {
+    base.checked_add(offset).ok_or_else(|| Error::Archive {
+        reason: format!("offset {offset} from {base} overflows"),
+    })
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, Error> {
+    let mut value = 0;
+
+    for shift in (0..64).step_by(7) {
+        let Some((&byte, rest)) = bytes.split_first() else {
+            break;
+        };
+
+        *bytes = rest;
+        value |= ((byte & 0x7f) as u64) << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(Error::Archive {
+        reason: String::from("directory contains a malformed varint"),
+    })
+}
+
+fn decompress(bytes: Vec<u8>, compression: Compression) -> Result<Vec<u8>, Error> {
+    match compression {
+        Compression::None | Compression::Unknown => Ok(bytes),
+        Compression::Gzip => {
+            let mut decompressed = Vec::new();
+            GzDecoder::new(bytes.as_slice()).read_to_end(&mut decompressed)?;
+            Ok(decompressed)
+        }
+
+        Compression::Brotli | Compression::Zstd => Err(Error::Archive {
+            reason: format!("unsupported compression `{compression:?}`"),
+        }),
+    }
+}
+
+/// Reads `length` bytes at the given `offset` of an archive `archive_length` bytes long.
+/// Sections extending past the end of the archive are rejected before allocating for them, as their bounds come from the archive itself.
+fn read_at<R: Read + Seek>(
+    reader: &mut R,
+    archive_length: u64,
+    offset: u64,
+    length: u64,
+) -> Result<Vec<u8>, Error> {
+    if offset
+        .checked_add(length)
+        .is_none_or(|end| end > archive_length)
+    {
+        return Err(Error::Archive {
+            reason: format!(
+                "section of {length} bytes at offset {offset} extends past the end of the archive"
+            ),
+        });
+    }
+
+    let mut bytes = vec![0; length as usize];
+
+    reader.seek(SeekFrom::Start(offset))?;
+    reader.read_exact(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
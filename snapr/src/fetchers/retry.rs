@@ -0,0 +1,312 @@
+//! Contains a policy retrying failed tile fetches with exponential backoff, that can wrap any [`TileFetcher`].
+//! See [`Retry`] for more details.
+
+use std::{fmt, sync::Arc, thread, time::Duration};
+
+use image::DynamicImage;
+
+use crate::Error;
+
+use super::{BatchTileFetcher, IndividualTileFetcher, TileFetcher};
+
+#[cfg(feature = "tokio")]
+use std::future::Future;
+
+#[cfg(feature = "tokio")]
+use super::{AsyncBatchTileFetcher, AsyncIndividualTileFetcher, AsyncTileFetcher};
+
+/// Predicate deciding whether a failed fetch is retried, see [`Retry::with_retry_if`].
+type RetryIf = Arc<dyn Fn(&Error) -> bool + Send + Sync>;
+
+/// Policy retrying failed tile fetches, waiting an exponentially growing backoff between each attempt.
+///
+/// The backoff before the `n`th retry is the [`initial_backoff`](Self::with_initial_backoff) multiplied by the [`multiplier`](Self::with_multiplier) `n - 1` times,
+/// capped at the [`max_backoff`](Self::with_max_backoff).
+/// By default, every error except [`Error::TileNotFound`] is retried, see [`with_retry_if`](Self::with_retry_if) to configure which errors are retried.
+///
+/// [`Individual`](TileFetcher::Individual) tile fetchers are retried tile by tile, while [`Batch`](TileFetcher::Batch) tile fetchers are retried as a whole batch.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::{sync::atomic::{AtomicU32, Ordering}, time::Duration};
+///
+/// use image::DynamicImage;
+/// use snapr::{fetchers::{retry::Retry, IndividualTileFetcher}, Error, TileFetcher};
+///
+/// static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+///
+/// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+///     match ATTEMPTS.fetch_add(1, Ordering::Relaxed) {
+///         0 | 1 => Err(Error::Unknown(anyhow::anyhow!("connection reset"))),
+///         _ => Ok(DynamicImage::new_rgba8(256, 256)),
+///     }
+/// }
+///
+/// let retry = Retry::new(3).with_initial_backoff(Duration::from_millis(1));
+///
+/// let TileFetcher::Individual(tile_fetcher) = retry.wrap(TileFetcher::individual(tile_fetcher)) else {
+///     unreachable!()
+/// };
+///
+/// assert!(tile_fetcher.fetch_tile(0, 0, 0).is_ok());
+/// assert_eq!(ATTEMPTS.load(Ordering::Relaxed), 3);
+/// ```
+#[derive(Clone)]
+pub struct Retry {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    retry_if: RetryIf,
+}
+
+impl Retry {
+    /// Constructs a new [`Retry`] policy, retrying a failed fetch up to `max_retries` times.
+    /// Backoff starts at `100ms`, doubles after each retry, and is capped at `10s`.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            retry_if: Arc::new(|error| !matches!(error, Error::TileNotFound { .. })),
+        }
+    }
+
+    /// Configures the backoff waited before the first retry.
+    pub fn with_initial_backoff(self, initial_backoff: Duration) -> Self {
+        Self {
+            initial_backoff,
+            ..self
+        }
+    }
+
+    /// Configures the longest backoff waited between two attempts.
+    pub fn with_max_backoff(self, max_backoff: Duration) -> Self {
+        Self {
+            max_backoff,
+            ..self
+        }
+    }
+
+    /// Configures the factor the backoff grows by after each retry, values below `1.0` are treated as `1.0`.
+    pub fn with_multiplier(self, multiplier: f64) -> Self {
+        Self {
+            multiplier: multiplier.max(1.0),
+            ..self
+        }
+    }
+
+    /// Configures the predicate deciding which errors are retried, other errors are returned immediately.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use snapr::{fetchers::retry::Retry, Error};
+    ///
+    /// let retry = Retry::new(5).with_retry_if(|error| matches!(error, Error::Io(_)));
+    /// ```
+    pub fn with_retry_if<F>(self, retry_if: F) -> Self
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            retry_if: Arc::new(retry_if),
+            ..self
+        }
+    }
+
+    /// Returns the backoff waited before the retry following the given zero-based `attempt`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use snapr::fetchers::retry::Retry;
+    ///
+    /// let retry = Retry::new(5)
+    ///     .with_initial_backoff(Duration::from_secs(1))
+    ///     .with_max_backoff(Duration::from_secs(5));
+    ///
+    /// assert_eq!(retry.backoff(0), Duration::from_secs(1));
+    /// assert_eq!(retry.backoff(2), Duration::from_secs(4));
+    /// assert_eq!(retry.backoff(3), Duration::from_secs(5));
+    /// ```
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self.initial_backoff.as_secs_f64()
+            * self.multiplier.powi(attempt.min(i32::MAX as u32) as i32);
+
+        Duration::from_secs_f64(backoff.min(self.max_backoff.as_secs_f64()))
+    }
+
+    /// Wraps the given `tile_fetcher`, retrying its failed fetches according to the [`Retry`] policy.
+    /// The returned [`TileFetcher`] is of the same variant as the given `tile_fetcher`.
+    pub fn wrap<'a>(&self, tile_fetcher: TileFetcher<'a>) -> TileFetcher<'a> {
+        match tile_fetcher {
+            TileFetcher::Individual(tile_fetcher) => TileFetcher::individual(RetryIndividual {
+                tile_fetcher,
+                retry: self.clone(),
+            }),
+
+            TileFetcher::Batch(tile_fetcher) => TileFetcher::batch(RetryBatch {
+                tile_fetcher,
+                retry: self.clone(),
+            }),
+        }
+    }
+
+    /// Wraps the given asynchronous `tile_fetcher`, retrying its failed fetches according to the [`Retry`] policy.
+    /// The returned [`AsyncTileFetcher`] is of the same variant as the given `tile_fetcher`.
+    ///
+    /// Backoffs are waited with [`tokio::time::sleep`], which requires the time driver of the runtime to be enabled.
+    #[cfg(feature = "tokio")]
+    pub fn wrap_async<'a>(&self, tile_fetcher: AsyncTileFetcher<'a>) -> AsyncTileFetcher<'a> {
+        match tile_fetcher {
+            AsyncTileFetcher::Individual(tile_fetcher) => {
+                AsyncTileFetcher::individual(RetryAsyncIndividual {
+                    tile_fetcher,
+                    retry: self.clone(),
+                })
+            }
+
+            AsyncTileFetcher::Batch(tile_fetcher) => AsyncTileFetcher::batch(RetryAsyncBatch {
+                tile_fetcher,
+                retry: self.clone(),
+            }),
+        }
+    }
+
+    /// Returns `true` if the `error` returned by the given zero-based `attempt` should be retried.
+    fn should_retry(&self, attempt: u32, error: &Error) -> bool {
+        let should_retry = attempt < self.max_retries && (self.retry_if)(error);
+
+        #[cfg(feature = "tracing")]
+        {
+            if should_retry {
+                tracing::warn!(attempt, %error, "retrying failed tile fetch");
+            }
+        }
+
+        should_retry
+    }
+
+    /// Runs the given `operation` until it succeeds, or fails with an error that shouldn't be retried.
+    fn run<T>(&self, mut operation: impl FnMut() -> Result<T, Error>) -> Result<T, Error> {
+        let mut attempt = 0;
+
+        loop {
+            match operation() {
+                Err(error) if self.should_retry(attempt, &error) => {
+                    thread::sleep(self.backoff(attempt));
+                    attempt += 1;
+                }
+
+                result => return result,
+            }
+        }
+    }
+
+    /// Runs the given asynchronous `operation` like [`run`](Self::run), without blocking the runtime while waiting.
+    #[cfg(feature = "tokio")]
+    async fn run_async<T, F, A>(&self, mut operation: F) -> Result<T, Error>
+    where
+        F: FnMut() -> A,
+        A: Future<Output = Result<T, Error>>,
+    {
+        let mut attempt = 0;
+
+        loop {
+            match operation().await {
+                Err(error) if self.should_retry(attempt, &error) => {
+                    tokio::time::sleep(self.backoff(attempt)).await;
+                    attempt += 1;
+                }
+
+                result => return result,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Retry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Retry")
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("multiplier", &self.multiplier)
+            .finish()
+    }
+}
+
+/// [`IndividualTileFetcher`] returned by [`Retry::wrap`].
+struct RetryIndividual<'a> {
+    tile_fetcher: Box<dyn IndividualTileFetcher + 'a>,
+    retry: Retry,
+}
+
+impl<'a> IndividualTileFetcher for RetryIndividual<'a> {
+    fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        self.retry.run(|| self.tile_fetcher.fetch_tile(x, y, zoom))
+    }
+}
+
+/// [`BatchTileFetcher`] returned by [`Retry::wrap`].
+struct RetryBatch<'a> {
+    tile_fetcher: Box<dyn BatchTileFetcher + 'a>,
+    retry: Retry,
+}
+
+impl<'a> BatchTileFetcher for RetryBatch<'a> {
+    fn fetch_tiles(
+        &self,
+        coordinate_matrix: &[(i32, i32)],
+        zoom: u8,
+    ) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+        self.retry
+            .run(|| self.tile_fetcher.fetch_tiles(coordinate_matrix, zoom))
+    }
+}
+
+/// [`AsyncIndividualTileFetcher`] returned by [`Retry::wrap_async`].
+#[cfg(feature = "tokio")]
+struct RetryAsyncIndividual {
+    tile_fetcher: Arc<dyn AsyncIndividualTileFetcher>,
+    retry: Retry,
+}
+
+#[cfg(feature = "tokio")]
+#[async_trait::async_trait]
+impl AsyncIndividualTileFetcher for RetryAsyncIndividual {
+    async fn fetch_tile(&self, x: i32, y: i32, zoom: u8) -> Result<DynamicImage, Error> {
+        self.retry
+            .run_async(|| self.tile_fetcher.fetch_tile(x, y, zoom))
+            .await
+    }
+}
+
+/// [`AsyncBatchTileFetcher`] returned by [`Retry::wrap_async`].
+#[cfg(feature = "tokio")]
+struct RetryAsyncBatch<'a> {
+    tile_fetcher: Box<dyn AsyncBatchTileFetcher + 'a>,
+    retry: Retry,
+}
+
+#[cfg(feature = "tokio")]
+#[async_trait::async_trait]
+impl<'a> AsyncBatchTileFetcher for RetryAsyncBatch<'a> {
+    async fn fetch_tiles(
+        &self,
+        coordinate_matrix: Vec<(i32, i32)>,
+        zoom: u8,
+    ) -> Result<Vec<(i32, i32, DynamicImage)>, Error> {
+        self.retry
+            .run_async(|| {
+                self.tile_fetcher
+                    .fetch_tiles(coordinate_matrix.clone(), zoom)
+            })
+            .await
+    }
+}
@@ -0,0 +1,156 @@
+use std::{fmt, ops::RangeInclusive};
+
+use tiny_skia::BlendMode;
+
+use crate::MissingTilePolicy;
+
+/// Layer of tiles composited on top of the basemap of a [`Snapr`](crate::Snapr), before any [`Drawable`](crate::drawing::Drawable) is rendered.
+///
+/// Layers are composited in the order they were added to the [`SnaprBuilder`](crate::SnaprBuilder), each with its own opacity and [`BlendMode`].
+/// Layers are skipped entirely when the zoom level their tiles would be fetched at is outside of their [`zoom_range`](Self::with_zoom_range).
+///
+/// Tiles of a layer that weren't fetched are left transparent by default, keeping the basemap underneath visible.
+/// This can be changed through [`with_missing_tile_policy`](Self::with_missing_tile_policy), independently of the [`MissingTilePolicy`] of the basemap.
+///
+/// ## Example
+///
+/// ```rust
+/// use image::DynamicImage;
+/// use snapr::{tiny_skia::BlendMode, SnaprBuilder, TileFetcher, TileLayer};
+///
+/// fn basemap(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+///     todo!("fetch tile's image from a basemap provider")
+/// }
+///
+/// fn hillshade(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+///     todo!("fetch tile's image from a hillshade provider")
+/// }
+///
+/// let snapr = SnaprBuilder::new()
+///     .with_tile_fetcher(TileFetcher::individual(basemap))
+///     .with_tile_layer(
+///         TileLayer::new(TileFetcher::individual(hillshade))
+///             .with_opacity(0.5)
+///             .with_blend_mode(BlendMode::Multiply)
+///             .with_zoom_range(0..=12),
+///     )
+///     .build();
+///
+/// assert!(snapr.is_ok());
+/// ```
+pub struct TileLayer<F> {
+    pub(crate) tile_fetcher: F,
+    pub(crate) opacity: f32,
+    pub(crate) blend_mode: BlendMode,
+    pub(crate) zoom_range: RangeInclusive<u8>,
+    pub(crate) missing_tile_policy: MissingTilePolicy,
+}
+
+impl<F> TileLayer<F> {
+    /// Constructs a new opaque [`TileLayer`] fetching its tiles from the given `tile_fetcher`, rendered at every zoom level.
+    pub fn new(tile_fetcher: F) -> Self {
+        Self {
+            tile_fetcher,
+            opacity: 1.0,
+            blend_mode: BlendMode::default(),
+            zoom_range: 0..=u8::MAX,
+            missing_tile_policy: MissingTilePolicy::Transparent,
+        }
+    }
+
+    /// Configures the opacity the [`TileLayer`] is composited with, clamped between `0.0` and `1.0`.
+    pub fn with_opacity(self, opacity: f32) -> Self {
+        Self {
+            opacity: opacity.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Configures the [`BlendMode`] the [`TileLayer`] is composited with, defaults to [`BlendMode::SourceOver`].
+    pub fn with_blend_mode(self, blend_mode: BlendMode) -> Self {
+        Self { blend_mode, ..self }
+    }
+
+    /// Configures the range of zoom levels the tiles of the [`TileLayer`] are fetched at.
+    pub fn with_zoom_range(self, zoom_range: RangeInclusive<u8>) -> Self {
+        Self { zoom_range, ..self }
+    }
+
+    /// Configures how tiles of the [`TileLayer`] that weren't fetched are rendered, defaults to [`MissingTilePolicy::Transparent`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use image::{DynamicImage, Rgba, RgbaImage};
+    /// use snapr::{MissingTilePolicy, SnaprBuilder, TileFetcher, TileLayer, Viewport};
+    ///
+    /// fn basemap(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     Ok(RgbaImage::from_pixel(256, 256, Rgba([255, 255, 255, 255])).into())
+    /// }
+    ///
+    /// fn overlay(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     Err(snapr::Error::TileNotFound { x, y, zoom })
+    /// }
+    ///
+    /// let fill = MissingTilePolicy::Fill(Rgba([255, 0, 0, 255]));
+    /// let viewport = Viewport::new(geo::point!(x: 40.807997, y: -96.699724), 15.0);
+    ///
+    /// // Missing tiles of the overlay are left transparent, regardless of the policy of the basemap.
+    /// let snapr = SnaprBuilder::new()
+    ///     .with_tile_fetcher(TileFetcher::individual(basemap))
+    ///     .with_tile_layer(TileLayer::new(TileFetcher::individual(overlay)))
+    ///     .with_missing_tile_policy(fill.clone())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let snapshot = snapr.snapshot_with_viewport(Vec::new(), viewport).unwrap();
+    ///
+    /// assert_eq!(snapshot.get_pixel(400, 300), &Rgba([255, 255, 255, 255]));
+    /// assert!(!snapshot.missing_tiles().is_empty());
+    /// assert!(snapshot.missing_tiles().iter().all(|missing| missing.layer == Some(0)));
+    ///
+    /// let snapr = SnaprBuilder::new()
+    ///     .with_tile_fetcher(TileFetcher::individual(basemap))
+    ///     .with_tile_layer(TileLayer::new(TileFetcher::individual(overlay)).with_missing_tile_policy(fill))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let snapshot = snapr.snapshot_with_viewport(Vec::new(), viewport).unwrap();
+    ///
+    /// assert_eq!(snapshot.get_pixel(400, 300), &Rgba([255, 0, 0, 255]));
+    /// ```
+    pub fn with_missing_tile_policy(self, missing_tile_policy: MissingTilePolicy) -> Self {
+        Self {
+            missing_tile_policy,
+            ..self
+        }
+    }
+
+    /// Returns `true` if the [`TileLayer`] is rendered when its tiles are fetched at the given `zoom` level.
+    pub fn contains_zoom(&self, zoom: u8) -> bool {
+        self.zoom_range.contains(&zoom)
+    }
+
+    /// Maps the tile fetcher of the [`TileLayer`] with the given function `f`, keeping every other field.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn map_tile_fetcher<G>(self, f: impl FnOnce(F) -> G) -> TileLayer<G> {
+        TileLayer {
+            tile_fetcher: f(self.tile_fetcher),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            zoom_range: self.zoom_range,
+            missing_tile_policy: self.missing_tile_policy,
+        }
+    }
+}
+
+impl<F> fmt::Debug for TileLayer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TileLayer")
+            .field("opacity", &self.opacity)
+            .field("blend_mode", &self.blend_mode)
+            .field("zoom_range", &self.zoom_range)
+            .field("missing_tile_policy", &self.missing_tile_policy)
+            .finish()
+    }
+}
@@ -1,22 +1,27 @@
 #![doc = include_str!("../README.md")]
 
 use std::{
-    f64::consts::PI,
+    collections::{HashMap, HashSet},
     fmt,
     ops::{Deref, DerefMut},
+    sync::Arc,
 };
 
 use drawing::{Context, Drawable};
-use geo::{BoundingRect, Centroid, Coord, MapCoords};
-use image::imageops::overlay;
+use fetchers::TileCoord;
+use geo::{BoundingRect, Centroid, Coord, CoordsIter, MapCoords};
+use image::imageops::{overlay, resize, FilterType};
+use projection::{Projection, WebMercator};
 use thiserror::Error;
-use tiny_skia::Pixmap;
+use tiny_skia::{BlendMode, ColorU8, FilterQuality, Pixmap, PixmapPaint, Transform};
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
 pub use builder::SnaprBuilder;
 pub use fetchers::TileFetcher;
+pub use layer::TileLayer;
+pub use snapshot::{MissingTile, Snapshot, WorldFile};
 pub use {geo, image, tiny_skia};
 
 #[cfg(feature = "tokio")]
@@ -25,6 +30,9 @@ pub use fetchers::AsyncTileFetcher;
 mod builder;
 pub mod drawing;
 pub mod fetchers;
+mod layer;
+pub mod projection;
+mod snapshot;
 
 #[cfg(feature = "tokio")]
 pub mod tokio;
@@ -38,10 +46,40 @@ pub enum Error {
     #[error("failed to build structure")]
     Builder { reason: String },
 
-    /// Returned by [`Snapr`] when a fetched tile does not match the expected [`tile_size`](Snapr::tile_size).
+    /// Returned by [`Snapr`] when a fetched tile does not match the expected [`tile_size`](Snapr::tile_size) under [`TileSizePolicy::Reject`].
     #[error("incorrect tile size")]
     IncorrectTileSize { expected: u32, received: u32 },
 
+    /// Returned by tile fetchers when the requested tile doesn't exist in their source.
+    #[error("tile {zoom}/{x}/{y} was not found")]
+    TileNotFound { x: i32, y: i32, zoom: u8 },
+
+    /// Returned by [`TileCoord`](fetchers::TileCoord) when a tile can't be converted to another [`TileScheme`](fetchers::TileScheme),
+    /// as it's past [`TileCoord::MAX_ZOOM`](fetchers::TileCoord::MAX_ZOOM) or its column or row is outside of the tile matrix.
+    #[error("tile {zoom}/{x}/{y} cannot be addressed")]
+    InvalidTileCoord { x: i32, y: i32, zoom: u8 },
+
+    /// Returned by tile fetchers reading tiles from an archive when said archive is malformed or unsupported.
+    /// Contains an inner [`reason`](Error::Archive::reason) explaining the specifics of the error.
+    #[error("failed to read tile archive")]
+    Archive { reason: String },
+
+    /// Returned by [`VectorTileFetcher`](fetchers::mvt::VectorTileFetcher) when a vector tile is malformed.
+    /// Contains an inner [`reason`](Error::VectorTile::reason) explaining the specifics of the error.
+    #[cfg(feature = "mvt")]
+    #[error("failed to decode vector tile")]
+    VectorTile { reason: String },
+
+    /// Returned by [`HttpTileFetcher::with_header`](fetchers::http::HttpTileFetcher::with_header) when the name or value of a header isn't valid in HTTP.
+    #[cfg(feature = "http")]
+    #[error("header `{name}` has an invalid name or value")]
+    InvalidHeader { name: String },
+
+    /// Returned by [`HttpTileFetcher`](fetchers::http::HttpTileFetcher) when its template contains a `{s}` placeholder, but no subdomains were configured.
+    #[cfg(feature = "http")]
+    #[error("template contains a `{{s}}` placeholder without any subdomains to fill it with")]
+    MissingSubdomains,
+
     #[error("failed to construct path")]
     PathConstruction,
 
@@ -54,10 +92,41 @@ pub enum Error {
     #[error("failed to calculate a centroid for the geometry collection")]
     CentroidCalculation,
 
+    /// Returned by [`Snapr`] when a zoom level given through [`SnapshotOptions`] or a [`Viewport`] is not finite, is negative, or is above [`Zoom::MAX_LEVEL`].
+    #[error("zoom level {zoom} is not between 0 and {max}", max = Zoom::MAX_LEVEL)]
+    InvalidZoom { zoom: f64 },
+
+    /// Returned by [`Snapshot`] when georeferencing a snapshot rendered with a [`Projection`] that doesn't define a [`Crs`](projection::Crs).
+    #[error("projection does not define a coordinate reference system")]
+    MissingCrs,
+
     #[cfg(feature = "tokio")]
     #[error("inner panic of spawned asynchronous task")]
     AsynchronousTaskPanic,
 
+    /// Transparent errors returned from [`std::io`] functions.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// Transparent errors returned from [`image`] functions.
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+
+    /// Transparent errors returned from [`tiff`] functions.
+    #[cfg(feature = "geotiff")]
+    #[error(transparent)]
+    Tiff(#[from] tiff::TiffError),
+
+    /// Transparent errors returned from [`reqwest`] functions.
+    #[cfg(feature = "http")]
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+
+    /// Transparent errors returned from [`rusqlite`] functions.
+    #[cfg(feature = "mbtiles")]
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+
     /// Transparent errors returned from [`resvg::usvg`] functions.
     #[cfg(feature = "svg")]
     #[error(transparent)]
@@ -69,22 +138,70 @@ pub enum Error {
 }
 
 /// Used by [`Snapr`] to determine how the zoom level is calculated when generating snapshots.
-#[derive(Clone, Copy, Debug, Hash, PartialEq)]
+///
+/// Zoom levels are represented as [`f64s`](f64), fractional levels are rendered by fetching tiles at the whole level below
+/// and resampling them to the fractional scale.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Zoom {
-    /// Specifies that the zoom level should be automatically derived from the geometry extents.
-    /// Contains an inner [`u8`] that controls the max zoom level.
-    Automatic(u8),
+    /// Specifies that the zoom level should be automatically derived from the geometry extents, snapping to whole zoom levels.
+    /// Contains an inner [`f64`] that controls the max zoom level.
+    Automatic(f64),
+
+    /// Specifies that the zoom level should be automatically derived from the geometry extents, without snapping to whole zoom levels.
+    /// Contains an inner [`f64`] that controls the max zoom level.
+    AutomaticFractional(f64),
 
     /// Specifies that the zoom level should be constant across all snapshots.
-    Constant(u8),
+    Constant(f64),
+}
+
+impl Zoom {
+    /// Highest zoom level [`Snapr`] renders snapshots at, applying to both constant levels and the max level of automatic ones.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use image::DynamicImage;
+    /// use snapr::{SnaprBuilder, TileFetcher, Viewport};
+    ///
+    /// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     Ok(DynamicImage::new_rgba8(256, 256))
+    /// }
+    ///
+    /// let invalid = SnaprBuilder::new()
+    ///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+    ///     .with_zoom(32)
+    ///     .build();
+    ///
+    /// assert!(matches!(invalid, Err(snapr::Error::Builder { .. })));
+    ///
+    /// let snapr = SnaprBuilder::new()
+    ///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let center = geo::point!(x: 40.807997, y: -96.699724);
+    ///
+    /// for zoom in [-0.5, f64::NAN, 32.0] {
+    ///     let snapshot = snapr.snapshot_with_viewport(Vec::new(), Viewport::new(center, zoom));
+    ///     assert!(matches!(snapshot, Err(snapr::Error::InvalidZoom { .. })));
+    /// }
+    /// ```
+    pub const MAX_LEVEL: f64 = 24.0;
+
+    /// Returns `true` if the inner level is between `0.0` and [`MAX_LEVEL`](Self::MAX_LEVEL), which excludes non-finite levels.
+    pub(crate) fn is_valid(&self) -> bool {
+        is_valid_zoom(**self)
+    }
 }
 
 impl Deref for Zoom {
-    type Target = u8;
+    type Target = f64;
 
     fn deref(&self) -> &Self::Target {
         match self {
             Self::Automatic(inner) => inner,
+            Self::AutomaticFractional(inner) => inner,
             Self::Constant(inner) => inner,
         }
     }
@@ -94,6 +211,7 @@ impl DerefMut for Zoom {
     fn deref_mut(&mut self) -> &mut Self::Target {
         match self {
             Self::Automatic(inner) => inner,
+            Self::AutomaticFractional(inner) => inner,
             Self::Constant(inner) => inner,
         }
     }
@@ -101,16 +219,312 @@ impl DerefMut for Zoom {
 
 impl Default for Zoom {
     fn default() -> Self {
-        Zoom::Automatic(17)
+        Zoom::Automatic(17.0)
     }
 }
 
 impl From<u8> for Zoom {
     fn from(value: u8) -> Self {
+        Zoom::Constant(value as f64)
+    }
+}
+
+impl From<f64> for Zoom {
+    fn from(value: f64) -> Self {
         Zoom::Constant(value)
     }
 }
 
+/// Pins the center, zoom level, and bearing of a snapshot, bypassing the values derived from the drawn geometries.
+/// See [`Snapr::snapshot_with_viewport`] for more details.
+///
+/// ## Example
+///
+/// ```rust
+/// use snapr::Viewport;
+///
+/// let viewport = Viewport::new(geo::point!(x: 40.807997, y: -96.699724), 15.0);
+///
+/// assert_eq!(viewport.zoom, Some(15.0));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Viewport {
+    /// [`EPSG:4326`](https://epsg.io/4326) center of the snapshot.
+    /// When [`None`], the center is derived from the centroid of the drawn geometries.
+    pub center: Option<geo::Point>,
+
+    /// Zoom level of the snapshot, between `0.0` and [`Zoom::MAX_LEVEL`].
+    /// When [`None`], the zoom level is derived from the [`Snapr::zoom`] field.
+    pub zoom: Option<f64>,
+
+    /// Compass direction, in degrees clockwise from north, that points towards the top of the snapshot.
+    /// The backing tiles and [`Drawables`](Drawable) are rotated around the center accordingly.
+    pub bearing: f64,
+}
+
+impl Viewport {
+    /// Constructs a new [`Viewport`] pinned to the given `center` and `zoom` level.
+    pub fn new(center: geo::Point, zoom: f64) -> Self {
+        Self {
+            center: Some(center),
+            zoom: Some(zoom),
+            ..Self::default()
+        }
+    }
+
+    /// Returns a copy of the [`Viewport`] rotated to the given `bearing`.
+    /// See [`Viewport::bearing`] for more details.
+    pub fn with_bearing(self, bearing: f64) -> Self {
+        Self { bearing, ..self }
+    }
+}
+
+/// Overrides the dimensions, zoom level, and padding of a [`Snapr`] for a single snapshot, alongside the [`Viewport`] of said snapshot.
+/// Any field left as [`None`] falls back to the value configured through [`SnaprBuilder`].
+/// See [`Snapr::snapshot_with_options`] for more details.
+///
+/// ## Example
+///
+/// ```rust
+/// use snapr::{SnapshotOptions, Zoom};
+///
+/// let options = SnapshotOptions::default()
+///     .with_dimensions(200, 200)
+///     .with_zoom(Zoom::Automatic(15.0))
+///     .with_padding(8);
+///
+/// assert_eq!(options.width, Some(200));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SnapshotOptions {
+    /// Overrides the [`Snapr::width`] of the snapshot.
+    pub width: Option<u32>,
+
+    /// Overrides the [`Snapr::height`] of the snapshot.
+    pub height: Option<u32>,
+
+    /// Overrides the [`Snapr::zoom`] of the snapshot.
+    pub zoom: Option<Zoom>,
+
+    /// Overrides the [`Snapr::padding`] of the snapshot.
+    pub padding: Option<Padding>,
+
+    /// Pins the center, zoom level, and bearing of the snapshot, see [`Viewport`] for more details.
+    pub viewport: Viewport,
+}
+
+impl SnapshotOptions {
+    /// Returns a copy of the [`SnapshotOptions`] overriding the `width` and `height` of the snapshot.
+    pub fn with_dimensions(self, width: u32, height: u32) -> Self {
+        Self {
+            width: Some(width),
+            height: Some(height),
+            ..self
+        }
+    }
+
+    /// Returns a copy of the [`SnapshotOptions`] overriding the [`Zoom`] of the snapshot.
+    /// Levels outside of `0.0..=Zoom::MAX_LEVEL` fail the snapshot with [`Error::InvalidZoom`].
+    pub fn with_zoom<Z: Into<Zoom>>(self, zoom: Z) -> Self {
+        Self {
+            zoom: Some(zoom.into()),
+            ..self
+        }
+    }
+
+    /// Returns a copy of the [`SnapshotOptions`] overriding the [`Padding`] of the snapshot.
+    pub fn with_padding<P: Into<Padding>>(self, padding: P) -> Self {
+        Self {
+            padding: Some(padding.into()),
+            ..self
+        }
+    }
+
+    /// Returns a copy of the [`SnapshotOptions`] using the given [`Viewport`].
+    pub fn with_viewport(self, viewport: Viewport) -> Self {
+        Self { viewport, ..self }
+    }
+}
+
+/// Settings of a single snapshot, resolved from [`SnapshotOptions`] and the fields of [`Snapr`].
+#[derive(Clone, Copy, Debug)]
+struct Frame {
+    width: u32,
+    height: u32,
+    zoom: Zoom,
+    padding: Padding,
+}
+
+/// Pixel padding kept clear on each side of a snapshot when fitting geometries with [`Zoom::Automatic`].
+/// Padding that leaves no space within the snapshot fits geometries at zoom level `0`.
+///
+/// ## Example
+///
+/// ```rust
+/// use image::DynamicImage;
+/// use snapr::{Padding, SnaprBuilder, TileFetcher};
+///
+/// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+///     Ok(DynamicImage::new_rgba8(256, 256))
+/// }
+///
+/// let padding = Padding::from(500);
+///
+/// assert_eq!(padding, Padding { top: 500, right: 500, bottom: 500, left: 500 });
+///
+/// // Leaves no space at all within a 800x600 snapshot.
+/// let snapr = SnaprBuilder::new()
+///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+///     .with_width(800)
+///     .with_height(600)
+///     .with_padding(padding)
+///     .build()
+///     .unwrap();
+///
+/// let line = geo::LineString::from(vec![(40.0, -96.0), (41.0, -97.0)]);
+/// let snapshot = snapr.snapshot_from_geometry(line).unwrap();
+///
+/// assert_eq!(snapshot.zoom(), 0.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Padding {
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+    pub left: u32,
+}
+
+impl From<u32> for Padding {
+    fn from(value: u32) -> Self {
+        Self {
+            top: value,
+            right: value,
+            bottom: value,
+            left: value,
+        }
+    }
+}
+
+/// Order of the axes of the [`EPSG:4326`](https://epsg.io/4326) coordinates given to [`Snapr`], applied to every geometry and [`Viewport`].
+///
+/// ## Example
+///
+/// ```rust
+/// use snapr::AxisOrder;
+///
+/// let coord = AxisOrder::LatLon.to_lon_lat(geo::coord! { x: 40.807997, y: -96.699724 });
+///
+/// assert_eq!(coord, geo::coord! { x: -96.699724, y: 40.807997 });
+/// ```
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum AxisOrder {
+    /// The `x` axis is the latitude, and the `y` axis is the longitude.
+    #[default]
+    LatLon,
+
+    /// The `x` axis is the longitude, and the `y` axis is the latitude.
+    /// Matches the convention used by [`geo`], [GeoJSON](https://geojson.org), and most spatial databases.
+    LonLat,
+}
+
+impl AxisOrder {
+    /// Converts a `coord` in this [`AxisOrder`] to one where the `x` axis is the longitude, and the `y` axis is the latitude.
+    pub fn to_lon_lat(self, coord: geo::Coord) -> geo::Coord {
+        match self {
+            Self::LatLon => geo::coord! { x: coord.y, y: coord.x },
+            Self::LonLat => coord,
+        }
+    }
+
+    /// Inverse of [`to_lon_lat`](Self::to_lon_lat), converts a `coord` where the `x` axis is the longitude to one in this [`AxisOrder`].
+    pub fn from_lon_lat(self, coord: geo::Coord) -> geo::Coord {
+        // Swapping the axes is its own inverse.
+        self.to_lon_lat(coord)
+    }
+}
+
+/// Policy deciding how [`Snapr`] renders tiles that its [`TileFetcher`] failed to fetch.
+///
+/// Tiles left out of the tiles returned by a [`BatchTileFetcher`](fetchers::BatchTileFetcher) are treated as missing, without failing the snapshot.
+/// Tiles of a [`TileLayer`] follow the policy of that layer instead, see [`TileLayer::with_missing_tile_policy`].
+/// Every missing tile is listed in [`Snapshot::missing_tiles`], alongside the layer it belongs to.
+///
+/// ## Example
+///
+/// ```rust
+/// use image::DynamicImage;
+/// use snapr::{MissingTilePolicy, SnaprBuilder, TileFetcher, Viewport};
+///
+/// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+///     match x % 2 {
+///         0 => Ok(DynamicImage::new_rgba8(256, 256)),
+///         _ => Err(snapr::Error::TileNotFound { x, y, zoom }),
+///     }
+/// }
+///
+/// let snapr = SnaprBuilder::new()
+///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+///     .with_missing_tile_policy(MissingTilePolicy::Fill(image::Rgba([224, 224, 224, 255])))
+///     .build()
+///     .unwrap();
+///
+/// let viewport = Viewport::new(geo::point!(x: 40.807997, y: -96.699724), 15.0);
+/// let snapshot = snapr.snapshot_with_viewport(Vec::new(), viewport).unwrap();
+///
+/// assert!(!snapshot.missing_tiles().is_empty());
+/// assert!(snapshot.missing_tiles().iter().all(|missing| missing.coord.x % 2 == 1));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum MissingTilePolicy {
+    /// Fails the whole snapshot with the error of a tile that failed to be fetched.
+    #[default]
+    Fail,
+
+    /// Leaves tiles that failed to be fetched transparent.
+    Transparent,
+
+    /// Fills tiles that failed to be fetched with a solid color.
+    Fill(image::Rgba<u8>),
+
+    /// Draws a placeholder image in place of tiles that failed to be fetched, resized to the size of a tile when necessary.
+    Placeholder(image::RgbaImage),
+}
+
+/// Policy deciding how [`Snapr`] handles fetched tiles that don't match its `tile_size`.
+///
+/// Tiles matching the `tile_size`, or the `tile_size` multiplied by the `pixel_ratio`, are always accepted.
+///
+/// ## Example
+///
+/// ```rust
+/// use image::DynamicImage;
+/// use snapr::{SnaprBuilder, TileFetcher, TileSizePolicy, Viewport};
+///
+/// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+///     Ok(DynamicImage::new_rgba8(512, 512))
+/// }
+///
+/// let snapr = SnaprBuilder::new()
+///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+///     .with_tile_size_policy(TileSizePolicy::Reject)
+///     .build()
+///     .unwrap();
+///
+/// let viewport = Viewport::new(geo::point!(x: 40.807997, y: -96.699724), 15.0);
+/// let snapshot = snapr.snapshot_with_viewport(Vec::new(), viewport);
+///
+/// assert!(matches!(snapshot, Err(snapr::Error::IncorrectTileSize { expected: 256, received: 512 })));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum TileSizePolicy {
+    /// Rejects tiles of any other size with [`Error::IncorrectTileSize`], which is then handled according to the [`MissingTilePolicy`].
+    Reject,
+
+    /// Resamples tiles of any other size to the `tile_size`.
+    #[default]
+    Resample,
+}
+
 /// Utility structure to generate snapshots.
 /// Should be normally constructed through building with [`SnaprBuilder`].
 pub struct Snapr<'a> {
@@ -129,6 +543,29 @@ pub struct Snapr<'a> {
 
     /// Zoom level of generated snapshots.
     zoom: Zoom,
+
+    /// Padding kept clear around geometries when the [`zoom`](Self::zoom) is [`Zoom::Automatic`].
+    padding: Padding,
+
+    /// Order of the axes of coordinates given to the [`Snapr`].
+    axis_order: AxisOrder,
+
+    /// Projection and tile matrix set that the [`tile_fetcher`](Self::tile_fetcher) serves tiles in.
+    /// See [`Projection`] for more details.
+    projection: Arc<dyn Projection>,
+
+    /// Ratio between the physical pixels of generated snapshots and the logical pixels used by the [`width`](Self::width), [`height`](Self::height), and styles of [`Drawables`](Drawable).
+    pixel_ratio: f32,
+
+    /// Layers of tiles composited on top of the tiles of the [`tile_fetcher`](Self::tile_fetcher), in order.
+    /// See [`TileLayer`] for more details.
+    tile_layers: Vec<TileLayer<TileFetcher<'a>>>,
+
+    /// Policy deciding how tiles that failed to be fetched by the [`tile_fetcher`](Self::tile_fetcher) are rendered.
+    missing_tile_policy: MissingTilePolicy,
+
+    /// Policy deciding how tiles that don't match the [`tile_size`](Self::tile_size) are handled.
+    tile_size_policy: TileSizePolicy,
 }
 
 impl<'a> Snapr<'a> {
@@ -137,10 +574,7 @@ impl<'a> Snapr<'a> {
         feature = "tracing",
         tracing::instrument(level = "DEBUG", skip(self, drawable), err)
     )]
-    pub fn snapshot_from_drawable(
-        &self,
-        drawable: &dyn Drawable,
-    ) -> Result<image::RgbaImage, Error> {
+    pub fn snapshot_from_drawable(&self, drawable: &dyn Drawable) -> Result<Snapshot, Error> {
         let drawables = vec![drawable];
         self.snapshot_from_drawables(drawables)
     }
@@ -153,30 +587,153 @@ impl<'a> Snapr<'a> {
     pub fn snapshot_from_drawables(
         &self,
         drawables: Vec<&dyn Drawable>,
-    ) -> Result<image::RgbaImage, Error> {
-        let mut output_image = image::RgbaImage::new(self.width, self.height);
+    ) -> Result<Snapshot, Error> {
+        self.snapshot_with_viewport(drawables, Viewport::default())
+    }
+
+    /// Attempts to generate a snapshot from the [`Drawable`] objects, using the given [`Viewport`] to determine the center and zoom level.
+    ///
+    /// Any field left as [`None`] in the `viewport` falls back to being derived from the geometries of the `drawables`.
+    /// When both the center and zoom level are pinned, `drawables` may be empty or contain no spatial [`Drawables`](Drawable) at all.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use image::DynamicImage;
+    /// use snapr::{SnaprBuilder, TileFetcher, Viewport};
+    ///
+    /// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     Ok(DynamicImage::new_rgba8(256, 256))
+    /// }
+    ///
+    /// let snapr = SnaprBuilder::new()
+    ///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let viewport = Viewport::new(geo::point!(x: 40.807997, y: -96.699724), 15.0);
+    /// let snapshot = snapr.snapshot_with_viewport(Vec::new(), viewport);
+    ///
+    /// assert!(snapshot.is_ok());
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "DEBUG", skip(self, drawables), err)
+    )]
+    pub fn snapshot_with_viewport(
+        &self,
+        drawables: Vec<&dyn Drawable>,
+        viewport: Viewport,
+    ) -> Result<Snapshot, Error> {
+        let options = SnapshotOptions {
+            viewport,
+            ..SnapshotOptions::default()
+        };
+
+        self.snapshot_with_options(drawables, options)
+    }
+
+    /// Attempts to generate a snapshot from the [`Drawable`] objects, overriding the dimensions, zoom level, and padding with the given [`SnapshotOptions`].
+    ///
+    /// Enables snapshots of varying sizes to be generated with a single [`Snapr`], and by extension a single [`TileFetcher`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use image::DynamicImage;
+    /// use snapr::{SnaprBuilder, SnapshotOptions, TileFetcher, Viewport};
+    ///
+    /// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     Ok(DynamicImage::new_rgba8(256, 256))
+    /// }
+    ///
+    /// let snapr = SnaprBuilder::new()
+    ///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let options = SnapshotOptions::default()
+    ///     .with_dimensions(200, 200)
+    ///     .with_viewport(Viewport::new(geo::point!(x: 40.807997, y: -96.699724), 15.0));
+    ///
+    /// let thumbnail = snapr.snapshot_with_options(Vec::new(), options).unwrap();
+    ///
+    /// assert_eq!(thumbnail.dimensions(), (200, 200));
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "DEBUG", skip(self, drawables), err)
+    )]
+    pub fn snapshot_with_options(
+        &self,
+        drawables: Vec<&dyn Drawable>,
+        options: SnapshotOptions,
+    ) -> Result<Snapshot, Error> {
+        let SnapshotOptions { viewport, .. } = options;
+
+        let frame = Frame {
+            width: options.width.unwrap_or(self.width),
+            height: options.height.unwrap_or(self.height),
+            zoom: options.zoom.unwrap_or(self.zoom),
+            padding: options.padding.unwrap_or(self.padding),
+        };
+
+        if !frame.zoom.is_valid() {
+            return Err(Error::InvalidZoom { zoom: *frame.zoom });
+        }
+
+        if let Some(zoom) = viewport.zoom.filter(|&zoom| !is_valid_zoom(zoom)) {
+            return Err(Error::InvalidZoom { zoom });
+        }
+
+        let (width, height) = self.pixel_dimensions(&frame);
+        let mut output_image = image::RgbaImage::new(width, height);
 
         let geometries = drawables
             .iter()
             .flat_map(|drawable| drawable.as_geometry())
             .collect::<Vec<_>>();
 
-        let geometries = geo::GeometryCollection::from(geometries);
+        // Geometries crossing the antimeridian are shifted onto a continuous range of longitudes,
+        // enabling the centroid and bounding box to be calculated on the correct side of the world.
+        let geometries =
+            unwrap_antimeridian(geo::GeometryCollection::from(geometries), self.axis_order);
 
-        let Some(mut pixmap) = Pixmap::new(self.width, self.height) else {
+        let Some(mut pixmap) = Pixmap::new(width, height) else {
             return Err(Error::PixmapConstruction);
         };
 
-        let Some(center) = geometries.centroid() else {
-            return Err(Error::CentroidCalculation);
-        };
+        let bounding_box = geometries.bounding_rect();
+
+        let zoom = match (viewport.zoom, frame.zoom) {
+            (Some(level), _) | (None, Zoom::Constant(level)) => level,
+            (None, Zoom::Automatic(max_level)) => match bounding_box {
+                Some(bounding_box) => self
+                    .zoom_from_geometries(bounding_box, &frame, &viewport, max_level)
+                    .floor(),
 
-        let zoom = match self.zoom {
-            Zoom::Constant(level) => level,
-            Zoom::Automatic(max_level) => match geometries.bounding_rect() {
-                Some(bounding_box) => self.zoom_from_geometries(bounding_box, max_level),
                 None => return Err(Error::BoundingBoxCalculation),
             },
+
+            (None, Zoom::AutomaticFractional(max_level)) => match bounding_box {
+                Some(bounding_box) => {
+                    self.zoom_from_geometries(bounding_box, &frame, &viewport, max_level)
+                }
+
+                None => return Err(Error::BoundingBoxCalculation),
+            },
+        };
+
+        let center = match (viewport.center, viewport.zoom, frame.zoom, bounding_box) {
+            (Some(center), ..) => center,
+            (None, None, Zoom::Automatic(_) | Zoom::AutomaticFractional(_), Some(bounding_box)) => {
+                self.center_from_geometries(bounding_box, &frame, zoom, viewport.bearing)
+            }
+
+            (None, ..) => match geometries.centroid() {
+                Some(center) => center,
+                None => return Err(Error::CentroidCalculation),
+            },
         };
 
         #[cfg(feature = "tracing")]
@@ -190,7 +747,8 @@ impl<'a> Snapr<'a> {
             );
         }
 
-        self.overlay_backing_tiles(&mut output_image, center, zoom)?;
+        let missing_tiles =
+            self.overlay_backing_tiles(&mut output_image, center, zoom, viewport.bearing)?;
 
         drawables
             .iter()
@@ -200,6 +758,10 @@ impl<'a> Snapr<'a> {
                     snapr: self,
                     center,
                     zoom,
+                    bearing: viewport.bearing,
+                    pixel_ratio: self.pixel_ratio,
+                    width: frame.width,
+                    height: frame.height,
                     index,
                 };
 
@@ -219,7 +781,7 @@ impl<'a> Snapr<'a> {
             tracing::trace!("merging the tiles and `Drawables` render images together");
         }
 
-        let pixmap_image = image::ImageBuffer::from_fn(self.width, self.height, |x, y| {
+        let pixmap_image = image::ImageBuffer::from_fn(width, height, |x, y| {
             let pixel = pixmap.pixel(x, y)
                 .expect("pixel coordinates should exactly match across `image::ImageBuffer` and `tiny_skia::Pixmap` instances");
 
@@ -227,7 +789,20 @@ impl<'a> Snapr<'a> {
         });
 
         overlay(&mut output_image, &pixmap_image, 0, 0);
-        Ok(output_image)
+
+        Ok(Snapshot {
+            image: output_image,
+            center,
+            zoom,
+            bearing: viewport.bearing,
+            width: frame.width,
+            height: frame.height,
+            pixel_ratio: self.pixel_ratio,
+            tile_size: self.tile_size,
+            axis_order: self.axis_order,
+            projection: self.projection.clone(),
+            missing_tiles,
+        })
     }
 
     /// Attempts to generate a snapshot from the given [`Geometry`](geo::Geometry).
@@ -235,7 +810,7 @@ impl<'a> Snapr<'a> {
         feature = "tracing",
         tracing::instrument(level = "DEBUG", skip(self, geometry), err)
     )]
-    pub fn snapshot_from_geometry<G>(&self, geometry: G) -> Result<image::RgbaImage, Error>
+    pub fn snapshot_from_geometry<G>(&self, geometry: G) -> Result<Snapshot, Error>
     where
         G: Into<geo::Geometry>,
     {
@@ -251,7 +826,7 @@ impl<'a> Snapr<'a> {
     pub fn snapshot_from_geometries(
         &self,
         geometries: Vec<geo::Geometry>,
-    ) -> Result<image::RgbaImage, Error> {
+    ) -> Result<Snapshot, Error> {
         let geometries = geometries
             .iter()
             .map(|geometry| geometry as &dyn Drawable)
@@ -262,53 +837,195 @@ impl<'a> Snapr<'a> {
 
     /// Converts a [`EPSG:4326`](https://epsg.io/4326) coordinate to a [`EPSG:3857`](https://epsg.io/3857) reprojection of said coordinate.
     /// Do note, that if you're attempting to use this function to call an XYZ layer you'll need to truncate the given `point` to be [`i32s`](i32).
-    pub fn epsg_4326_to_epsg_3857(zoom: u8, point: geo::Point) -> geo::Point {
-        let point_as_rad = point.to_radians();
-        let n = (1 << zoom as i32) as f64;
+    /// The given `point` is always read in the [`AxisOrder::LatLon`] order.
+    ///
+    /// Fractional `zoom` levels scale the reprojection accordingly, with each tile-space unit spanning `2^-(zoom - zoom.floor())` tiles of the whole level below.
+    ///
+    /// Equivalent to projecting with [`WebMercator`], regardless of the [`projection`](Self::projection) being used.
+    pub fn epsg_4326_to_epsg_3857(zoom: f64, point: geo::Point) -> geo::Point {
+        WebMercator
+            .project(geo::coord! { x: point.y(), y: point.x() }, zoom)
+            .into()
+    }
 
-        geo::point!(
-            x: (n * (point.y() + 180.0) / 360.0),
-            y: (n * (1.0 - (point_as_rad.x().tan() + (1.0 / point_as_rad.x().cos())).ln() / PI) / 2.0)
-        )
+    /// Inverse of [`epsg_4326_to_epsg_3857`](Self::epsg_4326_to_epsg_3857), converts a [`EPSG:3857`](https://epsg.io/3857) tile-space `point` back into a [`EPSG:4326`](https://epsg.io/4326) coordinate.
+    /// The returned coordinate is always in the [`AxisOrder::LatLon`] order.
+    ///
+    /// Equivalent to unprojecting with [`WebMercator`], regardless of the [`projection`](Self::projection) being used.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use snapr::Snapr;
+    ///
+    /// let point = Snapr::epsg_3857_to_epsg_4326(1.0, geo::point!(x: 1.0, y: 1.0));
+    ///
+    /// assert_eq!(point, geo::point!(x: 0.0, y: 0.0));
+    /// ```
+    pub fn epsg_3857_to_epsg_4326(zoom: f64, point: geo::Point) -> geo::Point {
+        let coord = WebMercator.unproject(point.0, zoom);
+        geo::point!(x: coord.y, y: coord.x)
     }
 }
 
 impl<'a> Snapr<'a> {
-    /// Calculates the [`zoom`](Self::zoom) level to use when [`zoom`](Self::zoom) itself is [`None`].
+    /// Returns the dimensions of the given `frame` in physical pixels, see [`pixel_ratio`](Self::pixel_ratio).
+    fn pixel_dimensions(&self, frame: &Frame) -> (u32, u32) {
+        let width = (frame.width as f32 * self.pixel_ratio).round() as u32;
+        let height = (frame.height as f32 * self.pixel_ratio).round() as u32;
+
+        (width, height)
+    }
+
+    /// Returns the size of backing tiles in physical pixels, see [`pixel_ratio`](Self::pixel_ratio).
+    fn tile_pixels(&self) -> u32 {
+        (self.tile_size as f32 * self.pixel_ratio).round() as u32
+    }
+
+    /// Projects an [`EPSG:4326`](https://epsg.io/4326) `point` into the tile-space of the [`projection`](Self::projection) at the given `zoom` level.
+    pub(crate) fn project(&self, point: geo::Point, zoom: f64) -> geo::Point {
+        self.projection
+            .project(self.axis_order.to_lon_lat(point.0), zoom)
+            .into()
+    }
+
+    /// Inverse of [`project`](Self::project), converts a tile-space `point` back into an [`EPSG:4326`](https://epsg.io/4326) coordinate.
+    pub(crate) fn unproject(&self, point: geo::Point, zoom: f64) -> geo::Point {
+        let coord = self.projection.unproject(point.0, zoom);
+        self.axis_order.from_lon_lat(coord).into()
+    }
+
+    /// Calculates the [`zoom`](Self::zoom) level to use when [`zoom`](Self::zoom) itself is [`Zoom::Automatic`] or [`Zoom::AutomaticFractional`].
+    ///
+    /// The calculated level is the highest, possibly fractional, one where the `bounding_box` fits within the `frame` after applying its padding.
+    /// When the padding leaves no space for the `bounding_box`, the calculated level is `0`.
+    /// When the `viewport` pins a center, each side of the `bounding_box` has to fit within its respective half of the snapshot instead.
+    /// The corners of the `bounding_box` are rotated by the `viewport` bearing before being measured.
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(level = "TRACE", skip(self), ret)
     )]
-    fn zoom_from_geometries(&self, bounding_box: geo::Rect, max_zoom: u8) -> u8 {
-        let mut zoom = 1;
+    fn zoom_from_geometries(
+        &self,
+        bounding_box: geo::Rect,
+        frame: &Frame,
+        viewport: &Viewport,
+        max_zoom: f64,
+    ) -> f64 {
+        let Padding {
+            top,
+            right,
+            bottom,
+            left,
+        } = frame.padding;
+
+        let (top, right, bottom, left) = (top as f64, right as f64, bottom as f64, left as f64);
+        let (width, height) = (frame.width as f64, frame.height as f64);
+        let tile_size = self.tile_size as f64;
+
+        // Extents are measured at zoom level `0`, every following level doubles them.
+        let bounding_box = bounding_box.map_coords(|coords| {
+            let converted = self.project(geo::Point::from(coords), 0.0);
+
+            Coord {
+                x: converted.x() * tile_size,
+                y: converted.y() * tile_size,
+            }
+        });
 
-        for level in (0..=max_zoom).rev() {
-            let bounding_box = bounding_box.map_coords(|coords| {
-                let converted = Self::epsg_4326_to_epsg_3857(level, geo::Point::from(coords));
+        let origin = match viewport.center {
+            Some(center) => self.project(center, 0.0) * tile_size,
+            None => bounding_box.center().into(),
+        };
 
-                Coord {
-                    x: converted.x(),
-                    y: converted.y(),
-                }
-            });
+        // Extents of the rotated corners, relative to the `origin` they're rotated around.
+        let corners = bounding_box
+            .to_polygon()
+            .exterior()
+            .points()
+            .map(|corner| rotate(corner - origin, -viewport.bearing))
+            .collect::<Vec<_>>();
 
-            let distance = geo::coord! { x: bounding_box.max().x - bounding_box.min().x, y: bounding_box.min().y - bounding_box.max().y }
-                * self.tile_size as f64;
+        let min = corners
+            .iter()
+            .fold(Coord::from((f64::MAX, f64::MAX)), |min, corner| Coord {
+                x: min.x.min(corner.x()),
+                y: min.y.min(corner.y()),
+            });
 
-            let dimensions = geo::point!(x: self.width as f64, y: self.height as f64).0;
+        let max = corners
+            .iter()
+            .fold(Coord::from((f64::MIN, f64::MIN)), |max, corner| Coord {
+                x: max.x.max(corner.x()),
+                y: max.y.max(corner.y()),
+            });
 
-            if distance.x > dimensions.x || distance.y > dimensions.y {
-                continue;
-            }
+        // Pairs of available space and the extent that has to fit within it.
+        let constraints = match viewport.center {
+            Some(_) => vec![
+                (width / 2.0 - left, -min.x),
+                (width / 2.0 - right, max.x),
+                (height / 2.0 - top, -min.y),
+                (height / 2.0 - bottom, max.y),
+            ],
+
+            None => vec![
+                (width - left - right, max.x - min.x),
+                (height - top - bottom, max.y - min.y),
+            ],
+        };
 
-            zoom = level;
-            break;
-        }
+        // Padding leaving no space at all falls back to zoom level `0`, rather than producing a `NaN` level.
+        let zoom = constraints
+            .into_iter()
+            .filter(|&(_, extent)| extent > 0.0)
+            .map(|(available, extent)| match available > 0.0 {
+                true => (available / extent).log2(),
+                false => 0.0,
+            })
+            .fold(max_zoom, f64::min);
+
+        // Guards against floating point error pushing an exact fit just below a whole level.
+        (zoom + 1e-9).clamp(0.0, max_zoom.max(0.0))
+    }
 
-        zoom
+    /// Calculates the center to use when [`zoom`](Self::zoom) is [`Zoom::Automatic`], and no center has been pinned by a [`Viewport`].
+    ///
+    /// The `bounding_box` center is shifted by the difference between opposing sides of the `frame` padding,
+    /// so that the `bounding_box` ends up centered within the padded area of the snapshot once rotated by the `bearing`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "TRACE", skip(self), ret)
+    )]
+    fn center_from_geometries(
+        &self,
+        bounding_box: geo::Rect,
+        frame: &Frame,
+        zoom: f64,
+        bearing: f64,
+    ) -> geo::Point {
+        let min = self.project(bounding_box.min().into(), zoom);
+        let max = self.project(bounding_box.max().into(), zoom);
+
+        let offset = geo::point!(
+            x: (frame.padding.left as f64 - frame.padding.right as f64) / 2.0,
+            y: (frame.padding.top as f64 - frame.padding.bottom as f64) / 2.0,
+        );
+
+        let offset = rotate(offset, bearing) / self.tile_size as f64;
+
+        self.unproject((min + max) / 2.0 - offset, zoom)
     }
 
-    /// Fills the given `image` with tiles centered around the given `epsg_3857_center` point.
+    /// Fills the given `image` with tiles centered around the given `center` point.
+    ///
+    /// Tiles are fetched at the whole zoom level below the given `zoom`, stitched together, and resampled to match the fractional part of `zoom`.
+    /// Tiles matching the logical [`tile_size`](Self::tile_size) are upsampled to match the [`pixel_ratio`](Self::pixel_ratio) beforehand,
+    /// while tiles of any other size are handled according to the [`tile_size_policy`](Self::tile_size_policy).
+    /// When a `bearing` is given, enough tiles are fetched to cover the rotated corners of the `image`.
+    /// The [`tile_layers`](Self::tile_layers) rendered at the zoom level tiles are fetched at are then composited on top, in order.
+    ///
+    /// Returns the tiles of every layer that weren't fetched, and were substituted according to the [`MissingTilePolicy`] of their layer.
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(level = "TRACE", skip(self, image), err)
@@ -317,56 +1034,216 @@ impl<'a> Snapr<'a> {
         &self,
         image: &mut image::RgbaImage,
         center: geo::Point,
-        zoom: u8,
-    ) -> Result<(), Error> {
-        let required_rows = 0.5 * (self.height as f64) / (self.tile_size as f64);
-        let required_columns = 0.5 * (self.width as f64) / (self.tile_size as f64);
+        zoom: f64,
+        bearing: f64,
+    ) -> Result<Vec<MissingTile>, Error> {
+        // Zoom levels are validated before reaching here, clamping only guards the tile matrix math below.
+        let tile_zoom = zoom.floor().clamp(0.0, Zoom::MAX_LEVEL);
+        let scale = (zoom - tile_zoom).exp2();
+
+        let (width, height) = image.dimensions();
+        let tile_pixels = self.tile_pixels();
+
+        // Size of a single tile once resampled to the fractional zoom level.
+        let scaled_tile_size = tile_pixels as f64 * scale;
+
+        // Half extents of the `image` once rotated by the `bearing`.
+        let (sin, cos) = bearing.to_radians().sin_cos();
+        let half_width = 0.5 * (width as f64 * cos.abs() + height as f64 * sin.abs());
+        let half_height = 0.5 * (width as f64 * sin.abs() + height as f64 * cos.abs());
 
-        let epsg_3857_center = Self::epsg_4326_to_epsg_3857(zoom, center);
-        let n = 1 << zoom as i32;
+        let required_rows = half_height / scaled_tile_size;
+        let required_columns = half_width / scaled_tile_size;
 
-        let min_x = (epsg_3857_center.x() - required_columns).floor() as i32;
-        let min_y = (epsg_3857_center.y() - required_rows).floor() as i32;
-        let max_x = (epsg_3857_center.x() + required_columns).ceil() as i32;
-        let max_y = (epsg_3857_center.y() + required_rows).ceil() as i32;
+        let tile_center = self.project(center, tile_zoom);
+        let matrix_size = self.projection.matrix_size(tile_zoom);
+        let (columns, rows) = (matrix_size.x as i32, matrix_size.y as i32);
+        let tile_zoom = tile_zoom as u8;
+
+        let min_x = (tile_center.x() - required_columns).floor() as i32;
+        let min_y = (tile_center.y() - required_rows).floor() as i32;
+        let max_x = (tile_center.x() + required_columns).ceil() as i32;
+        let max_y = (tile_center.y() + required_rows).ceil() as i32;
 
         #[cfg(feature = "tracing")]
         {
             tracing::trace!(
                 required_rows,
                 required_columns,
-                ?epsg_3857_center,
+                tile_zoom,
+                scale,
+                bearing,
+                ?tile_center,
                 min = ?(min_x, min_y),
                 max = ?(max_x, max_y),
                 "calculated bounds and required variables"
             );
         }
 
-        let coordinate_matrix = (min_x..max_x)
-            .map(|x| (x, min_y..max_y))
-            .flat_map(|(x, y)| y.map(move |y| (x, y)));
+        let grid = TileGrid {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+            columns,
+            rows,
+            zoom: tile_zoom,
+            tile_pixels,
+        };
 
-        match self.tile_fetcher {
-            TileFetcher::Individual(ref tile_fetcher) => {
-                // Capture various fields in `self` to enable `x_y_to_tile` to automatically implement `Sync`
-                let (tile_fetcher, tile_size, height, width, zoom) =
-                    (tile_fetcher, self.tile_size, self.height, self.width, zoom);
+        let mut missing_tiles = Vec::new();
+        let mosaic = self.fetch_mosaic(
+            &self.tile_fetcher,
+            &self.missing_tile_policy,
+            &grid,
+            &mut missing_tiles,
+        )?;
+
+        let mut missing_tiles = missing_tiles
+            .into_iter()
+            .map(|coord| MissingTile { coord, layer: None })
+            .collect::<Vec<_>>();
+
+        // Position of the `mosaic` relative to the center of the `image`.
+        let origin =
+            (geo::Point::from((min_x as f64, min_y as f64)) - tile_center) * scaled_tile_size;
+
+        let transform = match scale == 1.0 && bearing == 0.0 {
+            true => {
+                let (x, y) = (
+                    (origin.x() + width as f64 / 2.0) as i64,
+                    (origin.y() + height as f64 / 2.0) as i64,
+                );
+
+                overlay(image, &mosaic, x, y);
+                Transform::from_translate(x as f32, y as f32)
+            }
+
+            false => {
+                #[cfg(feature = "tracing")]
+                {
+                    tracing::trace!(
+                        scale,
+                        bearing,
+                        "resampling stitched tiles to the fractional zoom level and bearing"
+                    );
+                }
+
+                let transform = Transform::from_scale(scale as f32, scale as f32)
+                    .post_translate(origin.x() as f32, origin.y() as f32)
+                    .post_rotate(-bearing as f32)
+                    .post_translate(width as f32 / 2.0, height as f32 / 2.0);
+
+                overlay_transformed(image, &mosaic, transform)?;
+                transform
+            }
+        };
+
+        for (index, tile_layer) in self.tile_layers.iter().enumerate() {
+            if !tile_layer.contains_zoom(tile_zoom) {
+                continue;
+            }
+
+            #[cfg(feature = "tracing")]
+            {
+                tracing::trace!(?tile_layer, "compositing `TileLayer` over the basemap");
+            }
+
+            let mut missing_layer_tiles = Vec::new();
+            let mosaic = self.fetch_mosaic(
+                &tile_layer.tile_fetcher,
+                &tile_layer.missing_tile_policy,
+                &grid,
+                &mut missing_layer_tiles,
+            )?;
+
+            missing_tiles.extend(missing_layer_tiles.into_iter().map(|coord| MissingTile {
+                coord,
+                layer: Some(index),
+            }));
+
+            composite_transformed(
+                image,
+                &mosaic,
+                transform,
+                tile_layer.opacity,
+                tile_layer.blend_mode,
+            )?;
+        }
+
+        Ok(missing_tiles)
+    }
+
+    /// Fetches the tiles of the given `grid` with the `tile_fetcher`, and stitches them into a single mosaic at their native size.
+    /// Tiles that weren't fetched are appended to `missing_tiles`, and substituted according to the given `missing_tile_policy`.
+    fn fetch_mosaic(
+        &self,
+        tile_fetcher: &TileFetcher<'a>,
+        missing_tile_policy: &MissingTilePolicy,
+        grid: &TileGrid,
+        missing_tiles: &mut Vec<TileCoord>,
+    ) -> Result<image::RgbaImage, Error> {
+        let TileGrid {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+            columns,
+            rows,
+            zoom: tile_zoom,
+            tile_pixels,
+        } = *grid;
+
+        // Tiles are stitched into a single `mosaic` at their native size, before being resampled into the `image`.
+        let mut mosaic = image::RgbaImage::new(
+            (max_x - min_x) as u32 * tile_pixels,
+            (max_y - min_y) as u32 * tile_pixels,
+        );
+
+        // Projections without any tile at the zoom level leave the `mosaic` transparent, rather than wrapping columns by zero.
+        if columns <= 0 || rows <= 0 {
+            return Ok(mosaic);
+        }
+
+        // Positions of the `mosaic` within the rows of the tile matrix, alongside the coordinates of the tile drawn at them.
+        // Columns are wrapped around the antimeridian, while rows above or below the tile matrix are left transparent.
+        let positions = (min_x..max_x)
+            .flat_map(|x| (min_y.max(0)..max_y.min(rows)).map(move |y| (x, y)))
+            .map(|(x, y)| ((x, y), (x.rem_euclid(columns), y)))
+            .collect::<Vec<_>>();
+
+        // Snapshots wider than the world draw the same tile more than once, but only fetch it once.
+        let mut unique = HashSet::new();
+        let coordinate_matrix = positions
+            .iter()
+            .map(|&(_, coord)| coord)
+            .filter(|&coord| unique.insert(coord))
+            .collect::<Vec<_>>();
 
-                let x_y_to_tile =
-                    |(x, y): (i32, i32)| -> Result<(image::RgbaImage, i64, i64), Error> {
-                        let tile = tile_fetcher
-                            .fetch_tile((x + n) % n, (y + n) % n, zoom)?
-                            .to_rgba8();
+        // Fetched tiles keyed by their coordinates, or `None` for tiles that weren't fetched.
+        let mut tiles = HashMap::with_capacity(coordinate_matrix.len());
 
-                        let tile_coords = (geo::Point::from((x as f64, y as f64))
-                            - epsg_3857_center)
-                            .map_coords(|coord| geo::Coord {
-                                x: coord.x * tile_size as f64 + width as f64 / 2.0,
-                                y: coord.y * tile_size as f64 + height as f64 / 2.0,
-                            });
+        let mut insert_tile = |(x, y): (i32, i32), tile: Option<image::RgbaImage>| {
+            if tile.is_none() {
+                missing_tiles.push(TileCoord::new(x, y, tile_zoom));
+            }
+
+            tiles.insert((x, y), tile);
+        };
 
-                        Ok((tile, tile_coords.x() as i64, tile_coords.y() as i64))
-                    };
+        match tile_fetcher {
+            TileFetcher::Individual(tile_fetcher) => {
+                // Capture various fields in `self` to enable `x_y_to_tile` to automatically implement `Sync`
+                let (tile_fetcher, tile_size, tile_size_policy) =
+                    (tile_fetcher, self.tile_size, self.tile_size_policy);
+
+                let x_y_to_tile = |(x, y): (i32, i32)| {
+                    let tile = tile_fetcher.fetch_tile(x, y, tile_zoom).and_then(|tile| {
+                        normalize_tile(tile.to_rgba8(), tile_size, tile_pixels, tile_size_policy)
+                    });
+
+                    ((x, y), tile)
+                };
 
                 #[cfg(feature = "rayon")]
                 {
@@ -377,12 +1254,19 @@ impl<'a> Snapr<'a> {
                         );
                     }
 
-                    coordinate_matrix
-                        .par_bridge()
-                        .flat_map(x_y_to_tile)
+                    for ((x, y), tile) in coordinate_matrix
+                        .par_iter()
+                        .copied()
+                        .map(x_y_to_tile)
                         .collect::<Vec<_>>()
-                        .into_iter()
-                        .for_each(|(tile, x, y)| overlay(image, &tile, x, y));
+                    {
+                        let tile = fetched_tile_or_missing(
+                            missing_tile_policy,
+                            TileCoord::new(x, y, tile_zoom),
+                            tile,
+                        )?;
+                        insert_tile((x, y), tile);
+                    }
                 }
 
                 #[cfg(not(feature = "rayon"))]
@@ -392,34 +1276,88 @@ impl<'a> Snapr<'a> {
                         tracing::trace!("executing `TileFetcher::Individual` sequentially");
                     }
 
-                    for (x, y) in coordinate_matrix {
-                        let (tile, x, y) = x_y_to_tile((x, y))?;
-                        overlay(image, &tile, x, y);
+                    for ((x, y), tile) in coordinate_matrix.iter().copied().map(x_y_to_tile) {
+                        let tile = fetched_tile_or_missing(
+                            missing_tile_policy,
+                            TileCoord::new(x, y, tile_zoom),
+                            tile,
+                        )?;
+                        insert_tile((x, y), tile);
                     }
                 }
             }
 
-            TileFetcher::Batch(ref tile_fetcher) => {
-                let coordinate_matrix = coordinate_matrix.collect::<Vec<_>>();
-
+            TileFetcher::Batch(tile_fetcher) => {
                 #[cfg(feature = "tracing")]
                 {
                     tracing::trace!("executing `TileFetcher::Batch`");
                 }
 
-                for (x, y, tile) in tile_fetcher.fetch_tiles(&coordinate_matrix, zoom)? {
-                    let tile_coords = (geo::Point::from((x as f64, y as f64)) - epsg_3857_center)
-                        .map_coords(|coord| geo::Coord {
-                            x: coord.x * self.tile_size as f64 + self.width as f64 / 2.0,
-                            y: coord.y * self.tile_size as f64 + self.height as f64 / 2.0,
-                        });
+                let fetched = match tile_fetcher.fetch_tiles(&coordinate_matrix, tile_zoom) {
+                    Ok(tiles) => tiles,
+                    Err(error) if *missing_tile_policy == MissingTilePolicy::Fail => {
+                        return Err(error);
+                    }
+
+                    Err(_error) => {
+                        #[cfg(feature = "tracing")]
+                        {
+                            tracing::warn!(error = %_error, "substituting every tile of the failed batch");
+                        }
+
+                        Vec::new()
+                    }
+                };
+
+                let mut unfetched = coordinate_matrix.iter().copied().collect::<HashSet<_>>();
+
+                for (x, y, tile) in fetched {
+                    if !unfetched.remove(&(x, y)) {
+                        continue;
+                    }
+
+                    let tile = normalize_tile(
+                        tile.to_rgba8(),
+                        self.tile_size,
+                        tile_pixels,
+                        self.tile_size_policy,
+                    );
 
-                    overlay(image, &tile, tile_coords.x() as i64, tile_coords.y() as i64);
+                    let tile = fetched_tile_or_missing(
+                        missing_tile_policy,
+                        TileCoord::new(x, y, tile_zoom),
+                        tile,
+                    )?;
+                    insert_tile((x, y), tile);
+                }
+
+                // Tiles left out of the batch are missing, even though the batch itself didn't fail.
+                for &coord in coordinate_matrix
+                    .iter()
+                    .filter(|coord| unfetched.contains(coord))
+                {
+                    insert_tile(coord, None);
                 }
             }
         }
 
-        Ok(())
+        // Image substituted for tiles that weren't fetched, according to the `missing_tile_policy`.
+        let substitute = missing_tile_substitute(missing_tile_policy, tile_pixels);
+
+        for ((x, y), coord) in positions {
+            let offset_x = (x - min_x) as i64 * tile_pixels as i64;
+            let offset_y = (y - min_y) as i64 * tile_pixels as i64;
+
+            if let Some(tile) = tiles
+                .get(&coord)
+                .and_then(Option::as_ref)
+                .or(substitute.as_ref())
+            {
+                overlay(&mut mosaic, tile, offset_x, offset_y);
+            }
+        }
+
+        Ok(mosaic)
     }
 }
 
@@ -427,9 +1365,277 @@ impl<'a> fmt::Debug for Snapr<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Snapr")
             .field("tile_size", &self.tile_size)
+            .field("tile_layers", &self.tile_layers)
             .field("height", &self.height)
             .field("width", &self.width)
             .field("zoom", &self.zoom)
+            .field("padding", &self.padding)
+            .field("pixel_ratio", &self.pixel_ratio)
+            .field("axis_order", &self.axis_order)
+            .field("missing_tile_policy", &self.missing_tile_policy)
+            .field("tile_size_policy", &self.tile_size_policy)
             .finish()
     }
 }
+
+/// Returns the fetched `tile` at the given `coord`, or [`None`] if it failed to be fetched and should be substituted.
+/// Fails with the error of the `tile` under [`MissingTilePolicy::Fail`].
+fn fetched_tile_or_missing(
+    missing_tile_policy: &MissingTilePolicy,
+    _coord: TileCoord,
+    tile: Result<image::RgbaImage, Error>,
+) -> Result<Option<image::RgbaImage>, Error> {
+    match (tile, missing_tile_policy) {
+        (Ok(tile), _) => Ok(Some(tile)),
+        (Err(error), MissingTilePolicy::Fail) => Err(error),
+        (Err(_error), _) => {
+            #[cfg(feature = "tracing")]
+            {
+                tracing::warn!(coord = ?_coord, error = %_error, "substituting tile that failed to be fetched");
+            }
+
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the image drawn in place of missing tiles according to the given `missing_tile_policy`,
+/// or [`None`] if they're left transparent.
+fn missing_tile_substitute(
+    missing_tile_policy: &MissingTilePolicy,
+    tile_pixels: u32,
+) -> Option<image::RgbaImage> {
+    match missing_tile_policy {
+        MissingTilePolicy::Fail | MissingTilePolicy::Transparent => None,
+        MissingTilePolicy::Fill(color) => Some(image::RgbaImage::from_pixel(
+            tile_pixels,
+            tile_pixels,
+            *color,
+        )),
+
+        MissingTilePolicy::Placeholder(placeholder) => {
+            match placeholder.dimensions() == (tile_pixels, tile_pixels) {
+                true => Some(placeholder.clone()),
+                false => Some(resize(
+                    placeholder,
+                    tile_pixels,
+                    tile_pixels,
+                    FilterType::Triangle,
+                )),
+            }
+        }
+    }
+}
+
+/// Range of tiles covering a snapshot, shared by every layer of tiles composited into it.
+#[derive(Clone, Copy, Debug)]
+struct TileGrid {
+    min_x: i32,
+    min_y: i32,
+    max_x: i32,
+    max_y: i32,
+
+    /// Number of columns in the tile matrix, used to wrap tiles around the antimeridian.
+    columns: i32,
+
+    /// Number of rows in the tile matrix, rows outside of it are left transparent.
+    rows: i32,
+
+    zoom: u8,
+    tile_pixels: u32,
+}
+
+/// Shifts the longitudes of the given `geometries` onto the shortest continuous range covering all of them.
+///
+/// When that range crosses the antimeridian, longitudes west of it are moved east by `360` degrees,
+/// resulting in longitudes greater than `180` degrees which the projection handles by wrapping around the world.
+fn unwrap_antimeridian(
+    geometries: geo::GeometryCollection,
+    axis_order: AxisOrder,
+) -> geo::GeometryCollection {
+    let mut longitudes = geometries
+        .coords_iter()
+        .map(|coord| (axis_order.to_lon_lat(coord).x + 180.0).rem_euclid(360.0) - 180.0)
+        .collect::<Vec<_>>();
+
+    longitudes.sort_by(f64::total_cmp);
+
+    let (Some(&first), Some(&last)) = (longitudes.first(), longitudes.last()) else {
+        return geometries;
+    };
+
+    // The largest gap between consecutive longitudes is the part of the world left uncovered,
+    // unless that gap is the one wrapping around the antimeridian there's nothing to shift.
+    let (gap, start) = longitudes
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0], pair[1]))
+        .fold((first + 360.0 - last, first), |largest, gap| {
+            if gap.0 > largest.0 {
+                gap
+            } else {
+                largest
+            }
+        });
+
+    if start == first || gap <= 0.0 {
+        return geometries;
+    }
+
+    geometries.map_coords(|coord| {
+        let coord = axis_order.to_lon_lat(coord);
+        let longitude = (coord.x + 180.0).rem_euclid(360.0) - 180.0;
+
+        axis_order.from_lon_lat(Coord {
+            x: if longitude < start {
+                longitude + 360.0
+            } else {
+                longitude
+            },
+            y: coord.y,
+        })
+    })
+}
+
+/// Returns `true` if the given zoom `level` is between `0.0` and [`Zoom::MAX_LEVEL`].
+/// Non-finite levels are rejected, as `NaN` is never contained in the range.
+fn is_valid_zoom(level: f64) -> bool {
+    (0.0..=Zoom::MAX_LEVEL).contains(&level)
+}
+
+/// Rotates a `point` around the origin by the given `degrees`, clockwise in pixel space where `y` points down.
+pub(crate) fn rotate(point: geo::Point, degrees: f64) -> geo::Point {
+    if degrees == 0.0 {
+        return point;
+    }
+
+    let (sin, cos) = degrees.to_radians().sin_cos();
+
+    geo::point!(
+        x: point.x() * cos - point.y() * sin,
+        y: point.x() * sin + point.y() * cos,
+    )
+}
+
+/// Resizes a `tile` to be `tile_pixels` wide and tall.
+///
+/// Tiles of the logical `tile_size` are upsampled, while tiles already `tile_pixels` wide and tall, such as ones fetched at a higher resolution, are returned as is.
+/// Tiles of any other size are resampled or rejected according to the given `tile_size_policy`.
+fn normalize_tile(
+    tile: image::RgbaImage,
+    tile_size: u32,
+    tile_pixels: u32,
+    tile_size_policy: TileSizePolicy,
+) -> Result<image::RgbaImage, Error> {
+    let (width, height) = tile.dimensions();
+
+    if (width, height) == (tile_pixels, tile_pixels) {
+        return Ok(tile);
+    }
+
+    if (width, height) != (tile_size, tile_size) && tile_size_policy == TileSizePolicy::Reject {
+        let received = match width == tile_size || width == tile_pixels {
+            true => height,
+            false => width,
+        };
+
+        return Err(Error::IncorrectTileSize {
+            expected: tile_size,
+            received,
+        });
+    }
+
+    Ok(resize(
+        &tile,
+        tile_pixels,
+        tile_pixels,
+        FilterType::Triangle,
+    ))
+}
+
+/// Overlays the `source` image on top of the `destination` image after applying the given `transform` to it.
+/// The `source` is resampled with bilinear filtering.
+fn overlay_transformed(
+    destination: &mut image::RgbaImage,
+    source: &image::RgbaImage,
+    transform: Transform,
+) -> Result<(), Error> {
+    let source = image_to_pixmap(source)?;
+
+    let Some(mut pixmap) = Pixmap::new(destination.width(), destination.height()) else {
+        return Err(Error::PixmapConstruction);
+    };
+
+    pixmap.draw_pixmap(
+        0,
+        0,
+        source.as_ref(),
+        &PixmapPaint {
+            quality: FilterQuality::Bilinear,
+            ..PixmapPaint::default()
+        },
+        transform,
+        None,
+    );
+
+    overlay(destination, &pixmap_to_image(&pixmap), 0, 0);
+    Ok(())
+}
+
+/// Composites the `source` image into the `destination` image after applying the given `transform` to it,
+/// with the given `opacity` and `blend_mode`. The `source` is resampled with bilinear filtering.
+fn composite_transformed(
+    destination: &mut image::RgbaImage,
+    source: &image::RgbaImage,
+    transform: Transform,
+    opacity: f32,
+    blend_mode: BlendMode,
+) -> Result<(), Error> {
+    let source = image_to_pixmap(source)?;
+    let mut pixmap = image_to_pixmap(destination)?;
+
+    pixmap.draw_pixmap(
+        0,
+        0,
+        source.as_ref(),
+        &PixmapPaint {
+            opacity,
+            blend_mode,
+            quality: FilterQuality::Bilinear,
+        },
+        transform,
+        None,
+    );
+
+    *destination = pixmap_to_image(&pixmap);
+    Ok(())
+}
+
+/// Converts an [`RgbaImage`](image::RgbaImage) into a [`Pixmap`], premultiplying its pixels in the process.
+fn image_to_pixmap(image: &image::RgbaImage) -> Result<Pixmap, Error> {
+    let Some(mut pixmap) = Pixmap::new(image.width(), image.height()) else {
+        return Err(Error::PixmapConstruction);
+    };
+
+    for (pixel, &image::Rgba([red, green, blue, alpha])) in
+        pixmap.pixels_mut().iter_mut().zip(image.pixels())
+    {
+        *pixel = ColorU8::from_rgba(red, green, blue, alpha).premultiply();
+    }
+
+    Ok(pixmap)
+}
+
+/// Converts a [`Pixmap`] into an [`RgbaImage`](image::RgbaImage), demultiplying its pixels in the process.
+fn pixmap_to_image(pixmap: &Pixmap) -> image::RgbaImage {
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let pixel = pixel.demultiply();
+            [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+        })
+        .collect();
+
+    image::RgbaImage::from_raw(pixmap.width(), pixmap.height(), pixels)
+        .expect("buffer length should exactly match the dimensions of the `tiny_skia::Pixmap`")
+}
@@ -0,0 +1,169 @@
+//! Module containing definitions and implementations for projections and the tile matrix sets built on top of them.
+//! See [`Projection`] for more details.
+
+use std::f64::consts::PI;
+
+/// Types that represent a projection of [`EPSG:4326`](https://epsg.io/4326) coordinates onto a tile matrix set.
+///
+/// Coordinates in _tile-space_ are measured in tiles, with `(0, 0)` being the top-left corner of the tile at `x: 0` and `y: 0`.
+/// Implementations are expected to double the tile-space extents with every zoom level, fractional zoom levels scale accordingly.
+///
+/// ## Example
+///
+/// ```rust
+/// use snapr::projection::{Projection, WebMercator};
+///
+/// let tile = WebMercator.project(geo::coord! { x: 0.0, y: 0.0 }, 1.0);
+///
+/// assert_eq!(tile, geo::coord! { x: 1.0, y: 1.0 });
+/// ```
+pub trait Projection: Send + Sync {
+    /// Projects an [`EPSG:4326`](https://epsg.io/4326) `coord`, where `x` is the longitude and `y` is the latitude, into tile-space at the given `zoom` level.
+    fn project(&self, coord: geo::Coord, zoom: f64) -> geo::Coord;
+
+    /// Inverse of [`project`](Projection::project), converts a tile-space `coord` at the given `zoom` level back into an [`EPSG:4326`](https://epsg.io/4326) coordinate.
+    fn unproject(&self, coord: geo::Coord, zoom: f64) -> geo::Coord;
+
+    /// Returns the amount of tile columns (`x`) and rows (`y`) that cover the world at the given `zoom` level.
+    fn matrix_size(&self, zoom: f64) -> geo::Coord;
+
+    /// Returns the [`Crs`] that tile-space maps onto, used when georeferencing snapshots.
+    /// Projections without a known [`Crs`] can't be georeferenced, and return [`None`].
+    fn crs(&self) -> Option<Crs> {
+        None
+    }
+}
+
+/// Coordinate reference system that the tile-space of a [`Projection`] linearly maps onto.
+///
+/// ## Example
+///
+/// ```rust
+/// use snapr::projection::{Projection, WebMercator};
+///
+/// let crs = WebMercator.crs().unwrap();
+/// let origin = crs.from_tile_space(geo::coord! { x: 0.0, y: 0.0 }, 0.0);
+///
+/// assert_eq!(crs.epsg, 3857);
+/// assert_eq!(origin, geo::coord! { x: -20037508.342789244, y: 20037508.342789244 });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crs {
+    /// [EPSG](https://epsg.io) code of the coordinate reference system.
+    pub epsg: u16,
+
+    /// Whether the coordinate reference system is geographic, measured in degrees, rather than projected.
+    pub geographic: bool,
+
+    /// Definition of the coordinate reference system in the ESRI flavor of WKT, as expected in `.prj` files.
+    pub wkt: &'static str,
+
+    /// Position of the top-left corner of tile-space, in the units of the coordinate reference system.
+    pub origin: geo::Coord,
+
+    /// Extent of a single tile at zoom level `0`, in the units of the coordinate reference system.
+    /// The `y` axis is negative for coordinate reference systems where northings grow upwards.
+    pub tile_extent: geo::Coord,
+}
+
+impl Crs {
+    /// Converts a tile-space `coord` at the given `zoom` level into the units of the coordinate reference system.
+    pub fn from_tile_space(&self, coord: geo::Coord, zoom: f64) -> geo::Coord {
+        let n = zoom.exp2();
+
+        geo::coord! {
+            x: self.origin.x + coord.x / n * self.tile_extent.x,
+            y: self.origin.y + coord.y / n * self.tile_extent.y,
+        }
+    }
+}
+
+/// Radius of the sphere used by [`WebMercator`], in meters.
+const WEB_MERCATOR_RADIUS: f64 = 6378137.0;
+
+const WGS_84_WKT: &str = r#"GEOGCS["GCS_WGS_1984",DATUM["D_WGS_1984",SPHEROID["WGS_1984",6378137.0,298.257223563]],PRIMEM["Greenwich",0.0],UNIT["Degree",0.0174532925199433]]"#;
+
+const WEB_MERCATOR_WKT: &str = r#"PROJCS["WGS_1984_Web_Mercator_Auxiliary_Sphere",GEOGCS["GCS_WGS_1984",DATUM["D_WGS_1984",SPHEROID["WGS_1984",6378137.0,298.257223563]],PRIMEM["Greenwich",0.0],UNIT["Degree",0.0174532925199433]],PROJECTION["Mercator_Auxiliary_Sphere"],PARAMETER["False_Easting",0.0],PARAMETER["False_Northing",0.0],PARAMETER["Central_Meridian",0.0],PARAMETER["Standard_Parallel_1",0.0],PARAMETER["Auxiliary_Sphere_Type",0.0],UNIT["Meter",1.0]]"#;
+
+/// The [`EPSG:3857`](https://epsg.io/3857) _Web Mercator_ projection, used by XYZ tile providers such as [OpenStreetMap](https://www.openstreetmap.org).
+/// Covers the world with a single tile at zoom level `0`.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct WebMercator;
+
+impl Projection for WebMercator {
+    fn project(&self, coord: geo::Coord, zoom: f64) -> geo::Coord {
+        let n = zoom.exp2();
+        let latitude = coord.y.to_radians();
+
+        geo::coord! {
+            x: n * (coord.x + 180.0) / 360.0,
+            y: n * (1.0 - (latitude.tan() + (1.0 / latitude.cos())).ln() / PI) / 2.0,
+        }
+    }
+
+    fn unproject(&self, coord: geo::Coord, zoom: f64) -> geo::Coord {
+        let n = zoom.exp2();
+
+        geo::coord! {
+            x: coord.x / n * 360.0 - 180.0,
+            y: (PI * (1.0 - 2.0 * coord.y / n)).sinh().atan().to_degrees(),
+        }
+    }
+
+    fn matrix_size(&self, zoom: f64) -> geo::Coord {
+        let n = zoom.exp2();
+        geo::coord! { x: n, y: n }
+    }
+
+    fn crs(&self) -> Option<Crs> {
+        let half_circumference = PI * WEB_MERCATOR_RADIUS;
+
+        Some(Crs {
+            epsg: 3857,
+            geographic: false,
+            wkt: WEB_MERCATOR_WKT,
+            origin: geo::coord! { x: -half_circumference, y: half_circumference },
+            tile_extent: geo::coord! { x: 2.0 * half_circumference, y: -2.0 * half_circumference },
+        })
+    }
+}
+
+/// The _Plate Carrée_ projection of [`EPSG:4326`](https://epsg.io/4326), laid out like the `WorldCRS84Quad` WMTS tile matrix set.
+/// Covers the world with two tiles side-by-side at zoom level `0`, each spanning `180` degrees of longitude and latitude.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct PlateCarree;
+
+impl Projection for PlateCarree {
+    fn project(&self, coord: geo::Coord, zoom: f64) -> geo::Coord {
+        let n = zoom.exp2();
+
+        geo::coord! {
+            x: n * (coord.x + 180.0) / 180.0,
+            y: n * (90.0 - coord.y) / 180.0,
+        }
+    }
+
+    fn unproject(&self, coord: geo::Coord, zoom: f64) -> geo::Coord {
+        let n = zoom.exp2();
+
+        geo::coord! {
+            x: coord.x / n * 180.0 - 180.0,
+            y: 90.0 - coord.y / n * 180.0,
+        }
+    }
+
+    fn matrix_size(&self, zoom: f64) -> geo::Coord {
+        let n = zoom.exp2();
+        geo::coord! { x: 2.0 * n, y: n }
+    }
+
+    fn crs(&self) -> Option<Crs> {
+        Some(Crs {
+            epsg: 4326,
+            geographic: true,
+            wkt: WGS_84_WKT,
+            origin: geo::coord! { x: -180.0, y: 90.0 },
+            tile_extent: geo::coord! { x: 180.0, y: -180.0 },
+        })
+    }
+}
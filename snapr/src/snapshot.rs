@@ -0,0 +1,575 @@
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    path::Path,
+    sync::Arc,
+};
+
+#[cfg(feature = "geotiff")]
+use std::{
+    fs::File,
+    io::{BufWriter, Seek, Write},
+};
+
+use geo::Coord;
+
+use crate::{fetchers::TileCoord, projection::Projection, rotate, AxisOrder, Error};
+
+/// Result of generating a snapshot with [`Snapr`](crate::Snapr).
+///
+/// Contains the rendered image, alongside the center, zoom level, and bearing used to render it.
+/// Dereferences to the inner [`RgbaImage`](image::RgbaImage), so it can be used wherever an image is expected.
+///
+/// Pixels are measured in the physical pixels of the [`image`](Self::image), meaning they account for the [`pixel_ratio`](Self::pixel_ratio).
+/// Coordinates are [`EPSG:4326`](https://epsg.io/4326) coordinates in the [`AxisOrder`] of the [`Snapr`](crate::Snapr) that generated the snapshot.
+///
+/// ## Example
+///
+/// ```rust
+/// use geo::Intersects;
+/// use image::DynamicImage;
+/// use snapr::{SnaprBuilder, TileFetcher, Viewport};
+///
+/// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+///     Ok(DynamicImage::new_rgba8(256, 256))
+/// }
+///
+/// let snapr = SnaprBuilder::new()
+///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+///     .build()
+///     .unwrap();
+///
+/// let center = geo::point!(x: 40.807997, y: -96.699724);
+/// let snapshot = snapr.snapshot_with_viewport(Vec::new(), Viewport::new(center, 15.0)).unwrap();
+///
+/// assert_eq!(snapshot.coordinate_to_pixel(center.0), geo::coord! { x: 400.0, y: 300.0 });
+/// assert!(snapshot.bounds().intersects(&center));
+/// ```
+pub struct Snapshot {
+    pub(crate) image: image::RgbaImage,
+    pub(crate) center: geo::Point,
+    pub(crate) zoom: f64,
+    pub(crate) bearing: f64,
+
+    /// Width of the snapshot in logical pixels.
+    pub(crate) width: u32,
+
+    /// Height of the snapshot in logical pixels.
+    pub(crate) height: u32,
+
+    pub(crate) pixel_ratio: f32,
+    pub(crate) tile_size: u32,
+    pub(crate) axis_order: AxisOrder,
+    pub(crate) projection: Arc<dyn Projection>,
+    pub(crate) missing_tiles: Vec<MissingTile>,
+}
+
+impl Snapshot {
+    /// Returns a reference to the rendered image.
+    pub fn image(&self) -> &image::RgbaImage {
+        &self.image
+    }
+
+    /// Consumes the [`Snapshot`], returning the rendered image.
+    pub fn into_image(self) -> image::RgbaImage {
+        self.image
+    }
+
+    /// Returns the [`EPSG:4326`](https://epsg.io/4326) center of the snapshot.
+    pub fn center(&self) -> geo::Point {
+        self.center
+    }
+
+    /// Returns the zoom level the snapshot was rendered at.
+    pub fn zoom(&self) -> f64 {
+        self.zoom
+    }
+
+    /// Returns the bearing the snapshot was rendered at, see [`Viewport::bearing`](crate::Viewport::bearing).
+    pub fn bearing(&self) -> f64 {
+        self.bearing
+    }
+
+    /// Returns the ratio between the physical pixels of the [`image`](Self::image) and logical pixels.
+    pub fn pixel_ratio(&self) -> f32 {
+        self.pixel_ratio
+    }
+
+    /// Returns the tiles, of the basemap and every [`TileLayer`](crate::TileLayer), that weren't fetched,
+    /// and were substituted according to the [`MissingTilePolicy`](crate::MissingTilePolicy) of the layer they belong to.
+    pub fn missing_tiles(&self) -> &[MissingTile] {
+        &self.missing_tiles
+    }
+
+    /// Returns the [`EPSG:4326`](https://epsg.io/4326) bounding box covering every corner of the snapshot.
+    ///
+    /// When the snapshot crosses the antimeridian, the longitudes of the bounding box continue past `180` degrees instead of wrapping around.
+    pub fn bounds(&self) -> geo::Rect {
+        let (width, height) = self.image.dimensions();
+        let (width, height) = (width as f64, height as f64);
+
+        let mapping = self.mapping();
+        let corners = [(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)].map(|(x, y)| {
+            self.axis_order
+                .to_lon_lat(mapping.pixel_to_unwrapped_coordinate(Coord { x, y }))
+        });
+
+        let min = corners.iter().fold(corners[0], |min, corner| Coord {
+            x: min.x.min(corner.x),
+            y: min.y.min(corner.y),
+        });
+
+        let max = corners.iter().fold(corners[0], |max, corner| Coord {
+            x: max.x.max(corner.x),
+            y: max.y.max(corner.y),
+        });
+
+        geo::Rect::new(
+            self.axis_order.from_lon_lat(min),
+            self.axis_order.from_lon_lat(max),
+        )
+    }
+
+    /// Converts an [`EPSG:4326`](https://epsg.io/4326) coordinate to the pixel it's rendered at in the [`image`](Self::image).
+    /// The returned pixel may lie outside of the [`image`](Self::image).
+    pub fn coordinate_to_pixel(&self, coord: Coord) -> Coord {
+        self.mapping().coordinate_to_pixel(coord)
+    }
+
+    /// Inverse of [`coordinate_to_pixel`](Self::coordinate_to_pixel), converts a pixel in the [`image`](Self::image) to an [`EPSG:4326`](https://epsg.io/4326) coordinate.
+    /// Longitudes of the returned coordinate are wrapped to be within `-180` and `180` degrees.
+    pub fn pixel_to_coordinate(&self, pixel: Coord) -> Coord {
+        self.mapping().pixel_to_coordinate(pixel)
+    }
+
+    /// Returns the [`WorldFile`] georeferencing the [`image`](Self::image) in the [`Crs`](crate::projection::Crs) of the projection it was rendered with.
+    /// Returns [`None`] when said projection doesn't define a [`Crs`](crate::projection::Crs).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use image::DynamicImage;
+    /// use snapr::{SnaprBuilder, TileFetcher, Viewport};
+    ///
+    /// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     Ok(DynamicImage::new_rgba8(256, 256))
+    /// }
+    ///
+    /// let snapr = SnaprBuilder::new()
+    ///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+    ///     .with_width(256)
+    ///     .with_height(256)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// // A single tile covering the whole world, whose edges lie `20037508.34` meters away from the center in Web Mercator.
+    /// let viewport = Viewport::new(geo::point!(x: 0.0, y: 0.0), 0.0);
+    /// let world_file = snapr.snapshot_with_viewport(Vec::new(), viewport).unwrap().world_file().unwrap();
+    ///
+    /// let pixel = 2.0 * 20037508.342789244 / 256.0;
+    /// let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+    ///
+    /// assert!(close(world_file.x_scale, pixel) && close(world_file.y_scale, -pixel));
+    /// assert!(close(world_file.x_skew, 0.0) && close(world_file.y_skew, 0.0));
+    ///
+    /// // World files anchor on the center of the top-left pixel.
+    /// assert!(close(world_file.x, -20037508.342789244 + pixel / 2.0));
+    /// assert!(close(world_file.y, 20037508.342789244 - pixel / 2.0));
+    /// ```
+    pub fn world_file(&self) -> Option<WorldFile> {
+        let crs = self.projection.crs()?;
+
+        // Pixels map onto the coordinate reference system affinely, so three pixel centers are enough to derive the transform.
+        let mapping = self.mapping();
+        let origin =
+            crs.from_tile_space(mapping.pixel_to_tile(Coord { x: 0.5, y: 0.5 }), self.zoom);
+        let right = crs.from_tile_space(mapping.pixel_to_tile(Coord { x: 1.5, y: 0.5 }), self.zoom);
+        let down = crs.from_tile_space(mapping.pixel_to_tile(Coord { x: 0.5, y: 1.5 }), self.zoom);
+
+        Some(WorldFile {
+            x_scale: right.x - origin.x,
+            y_skew: right.y - origin.y,
+            x_skew: down.x - origin.x,
+            y_scale: down.y - origin.y,
+            x: origin.x,
+            y: origin.y,
+        })
+    }
+
+    /// Saves the [`image`](Self::image) to the given `path`, alongside a [`WorldFile`] and `.prj` file readable by GIS software such as [QGIS](https://qgis.org).
+    ///
+    /// The format of the image is derived from the extension of the `path`.
+    /// The extension of the [`WorldFile`] follows the ESRI convention of joining the first and last letters of the image extension with a `w`, e.g. `.pgw` for `.png` images.
+    ///
+    /// Returns [`Error::MissingCrs`] when the projection of the snapshot doesn't define a [`Crs`](crate::projection::Crs).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use image::DynamicImage;
+    /// use snapr::{SnaprBuilder, TileFetcher, Viewport};
+    ///
+    /// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     Ok(DynamicImage::new_rgba8(256, 256))
+    /// }
+    ///
+    /// let snapr = SnaprBuilder::new()
+    ///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let viewport = Viewport::new(geo::point!(x: 40.807997, y: -96.699724), 15.0);
+    /// let snapshot = snapr.snapshot_with_viewport(Vec::new(), viewport).unwrap();
+    ///
+    /// let root = std::env::temp_dir().join(format!("snapr-world-file-{}", std::process::id()));
+    /// std::fs::create_dir_all(&root).unwrap();
+    ///
+    /// snapshot.save_with_world_file(root.join("snapshot.png")).unwrap();
+    ///
+    /// let world_file = std::fs::read_to_string(root.join("snapshot.pgw")).unwrap();
+    /// assert_eq!(world_file, snapshot.world_file().unwrap().to_string());
+    /// assert!(std::fs::read_to_string(root.join("snapshot.prj")).unwrap().starts_with("PROJCS"));
+    ///
+    /// std::fs::remove_dir_all(root).unwrap();
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "DEBUG", skip(self, path), err)
+    )]
+    pub fn save_with_world_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+
+        let (Some(crs), Some(world_file)) = (self.projection.crs(), self.world_file()) else {
+            return Err(Error::MissingCrs);
+        };
+
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+
+        let world_file_extension = match (extension.chars().next(), extension.chars().last()) {
+            (Some(first), Some(last)) if extension.len() > 1 => format!("{first}{last}w"),
+            _ => String::from("wld"),
+        };
+
+        self.image.save(path)?;
+        std::fs::write(
+            path.with_extension(world_file_extension),
+            world_file.to_string(),
+        )?;
+        std::fs::write(path.with_extension("prj"), crs.wkt)?;
+
+        Ok(())
+    }
+
+    /// Writes the [`image`](Self::image) as a [GeoTIFF](https://www.ogc.org/standard/geotiff) to the given `writer`,
+    /// georeferenced in the [`Crs`](crate::projection::Crs) of the projection it was rendered with.
+    ///
+    /// Returns [`Error::MissingCrs`] when said projection doesn't define a [`Crs`](crate::projection::Crs).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    ///
+    /// use image::DynamicImage;
+    /// use snapr::{SnaprBuilder, TileFetcher, Viewport};
+    /// use tiff::{decoder::Decoder, tags::Tag};
+    ///
+    /// fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+    ///     Ok(DynamicImage::new_rgba8(256, 256))
+    /// }
+    ///
+    /// let snapr = SnaprBuilder::new()
+    ///     .with_tile_fetcher(TileFetcher::individual(tile_fetcher))
+    ///     .with_width(256)
+    ///     .with_height(256)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let viewport = Viewport::new(geo::point!(x: 0.0, y: 0.0), 0.0);
+    /// let snapshot = snapr.snapshot_with_viewport(Vec::new(), viewport).unwrap();
+    ///
+    /// let mut geotiff = Cursor::new(Vec::new());
+    /// snapshot.write_geotiff(&mut geotiff).unwrap();
+    /// geotiff.set_position(0);
+    ///
+    /// let mut decoder = Decoder::new(geotiff).unwrap();
+    /// assert_eq!(decoder.dimensions().unwrap(), (256, 256));
+    ///
+    /// // Pixels span `2 * 20037508.34 / 256` meters, with the top-left corner of the raster tied to the top-left corner of the world.
+    /// let pixel = 2.0 * 20037508.342789244 / 256.0;
+    /// let close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-6);
+    ///
+    /// let scale = decoder.get_tag_f64_vec(Tag::ModelPixelScaleTag).unwrap();
+    /// assert!(close(&scale, &[pixel, pixel, 0.0]));
+    ///
+    /// let tiepoint = decoder.get_tag_f64_vec(Tag::ModelTiepointTag).unwrap();
+    /// assert!(close(&tiepoint, &[0.0, 0.0, 0.0, -20037508.342789244, 20037508.342789244, 0.0]));
+    ///
+    /// // Projected model, with pixels covering an area, in `EPSG:3857`.
+    /// let geo_keys = decoder.get_tag_u16_vec(Tag::GeoKeyDirectoryTag).unwrap();
+    /// assert_eq!(&geo_keys[4..], [1024, 0, 1, 1, 1025, 0, 1, 1, 3072, 0, 1, 3857]);
+    /// ```
+    #[cfg(feature = "geotiff")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "DEBUG", skip(self, writer), err)
+    )]
+    pub fn write_geotiff<W: Write + Seek>(&self, writer: W) -> Result<(), Error> {
+        use tiff::{encoder::colortype::RGBA8, encoder::TiffEncoder, tags::Tag};
+
+        let (Some(crs), Some(world_file)) = (self.projection.crs(), self.world_file()) else {
+            return Err(Error::MissingCrs);
+        };
+
+        let mut encoder = TiffEncoder::new(writer)?;
+        let mut image = encoder.new_image::<RGBA8>(self.image.width(), self.image.height())?;
+
+        let WorldFile {
+            x_scale,
+            y_skew,
+            x_skew,
+            y_scale,
+            ..
+        } = world_file;
+
+        // GeoTIFF rasters anchor on the corner of the top-left pixel, rather than its center.
+        let x = world_file.x - (x_scale + x_skew) / 2.0;
+        let y = world_file.y - (y_skew + y_scale) / 2.0;
+
+        if x_skew == 0.0 && y_skew == 0.0 {
+            let scale = [x_scale, -y_scale, 0.0];
+            let tiepoint = [0.0, 0.0, 0.0, x, y, 0.0];
+
+            image
+                .encoder()
+                .write_tag(Tag::ModelPixelScaleTag, &scale[..])?;
+            image
+                .encoder()
+                .write_tag(Tag::ModelTiepointTag, &tiepoint[..])?;
+        } else {
+            #[rustfmt::skip]
+            let transformation = [
+                x_scale, x_skew, 0.0, x,
+                y_skew, y_scale, 0.0, y,
+                0.0, 0.0, 0.0, 0.0,
+                0.0, 0.0, 0.0, 1.0,
+            ];
+
+            image
+                .encoder()
+                .write_tag(Tag::ModelTransformationTag, &transformation[..])?;
+        }
+
+        // Keys are `GTModelTypeGeoKey`, `GTRasterTypeGeoKey`, and either `GeographicTypeGeoKey` or `ProjectedCSTypeGeoKey`.
+        let (model_type, crs_key) = match crs.geographic {
+            true => (2, 2048),
+            false => (1, 3072),
+        };
+
+        #[rustfmt::skip]
+        let geo_keys: [u16; 16] = [
+            1, 1, 0, 3,
+            1024, 0, 1, model_type,
+            1025, 0, 1, 1,
+            crs_key, 0, 1, crs.epsg,
+        ];
+
+        image
+            .encoder()
+            .write_tag(Tag::GeoKeyDirectoryTag, &geo_keys[..])?;
+        image.write_data(self.image.as_raw())?;
+
+        Ok(())
+    }
+
+    /// Saves the [`image`](Self::image) as a [GeoTIFF](https://www.ogc.org/standard/geotiff) to the given `path`.
+    /// See [`write_geotiff`](Self::write_geotiff) for more details.
+    #[cfg(feature = "geotiff")]
+    pub fn save_geotiff<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let file = File::create(path)?;
+        self.write_geotiff(BufWriter::new(file))
+    }
+
+    /// Returns the [`PixelMapping`] between coordinates and the pixels of the [`image`](Self::image).
+    fn mapping(&self) -> PixelMapping<'_> {
+        PixelMapping {
+            projection: &*self.projection,
+            axis_order: self.axis_order,
+            center: self.center,
+            zoom: self.zoom,
+            bearing: self.bearing,
+            width: self.width,
+            height: self.height,
+            pixel_ratio: self.pixel_ratio,
+            tile_size: self.tile_size,
+        }
+    }
+}
+
+impl Deref for Snapshot {
+    type Target = image::RgbaImage;
+
+    fn deref(&self) -> &Self::Target {
+        &self.image
+    }
+}
+
+impl DerefMut for Snapshot {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.image
+    }
+}
+
+impl From<Snapshot> for image::RgbaImage {
+    fn from(value: Snapshot) -> Self {
+        value.image
+    }
+}
+
+impl fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Snapshot")
+            .field("dimensions", &self.image.dimensions())
+            .field("center", &self.center)
+            .field("zoom", &self.zoom)
+            .field("bearing", &self.bearing)
+            .field("pixel_ratio", &self.pixel_ratio)
+            .field("missing_tiles", &self.missing_tiles)
+            .finish()
+    }
+}
+
+/// Tile that wasn't fetched while generating a [`Snapshot`], see [`Snapshot::missing_tiles`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct MissingTile {
+    /// Coordinates of the tile, as requested from the tile fetcher.
+    pub coord: TileCoord,
+
+    /// Index of the [`TileLayer`](crate::TileLayer) the tile belongs to, in the order layers were added to the [`Snapr`](crate::Snapr),
+    /// or [`None`] if the tile belongs to the basemap.
+    pub layer: Option<usize>,
+}
+
+/// Maps [`EPSG:4326`](https://epsg.io/4326) coordinates to the physical pixels of a snapshot and back.
+///
+/// Shared by [`Snapshot`] and [`Context`](crate::drawing::Context), so that pixels are derived identically while drawing and once drawn.
+#[derive(Clone, Copy)]
+pub(crate) struct PixelMapping<'a> {
+    pub(crate) projection: &'a dyn Projection,
+    pub(crate) axis_order: AxisOrder,
+    pub(crate) center: geo::Point,
+    pub(crate) zoom: f64,
+    pub(crate) bearing: f64,
+
+    /// Width of the snapshot in logical pixels.
+    pub(crate) width: u32,
+
+    /// Height of the snapshot in logical pixels.
+    pub(crate) height: u32,
+
+    pub(crate) pixel_ratio: f32,
+    pub(crate) tile_size: u32,
+}
+
+impl PixelMapping<'_> {
+    /// Converts an [`EPSG:4326`](https://epsg.io/4326) `coord` to the physical pixel it's rendered at.
+    /// Picks the copy of the world closest to the center, keeping geometries crossing the antimeridian continuous.
+    pub(crate) fn coordinate_to_pixel(&self, coord: Coord) -> Coord {
+        let tile_point = self.project(coord) - self.project(self.center.0);
+
+        let world_width = self.projection.matrix_size(self.zoom).x;
+        let offset_x = tile_point.x - world_width * (tile_point.x / world_width).round();
+
+        let offset = rotate(
+            geo::point!(x: offset_x, y: tile_point.y) * self.tile_size as f64,
+            -self.bearing,
+        );
+
+        let pixel_ratio = self.pixel_ratio as f64;
+
+        geo::coord! {
+            x: (offset.x() + self.width as f64 / 2.0) * pixel_ratio,
+            y: (offset.y() + self.height as f64 / 2.0) * pixel_ratio,
+        }
+    }
+
+    /// Inverse of [`coordinate_to_pixel`](Self::coordinate_to_pixel), with the longitude of the returned coordinate wrapped to be within `-180` and `180` degrees.
+    pub(crate) fn pixel_to_coordinate(&self, pixel: Coord) -> Coord {
+        let coord = self
+            .axis_order
+            .to_lon_lat(self.pixel_to_unwrapped_coordinate(pixel));
+
+        self.axis_order.from_lon_lat(Coord {
+            x: (coord.x + 180.0).rem_euclid(360.0) - 180.0,
+            y: coord.y,
+        })
+    }
+
+    /// Converts a physical `pixel` back into an [`EPSG:4326`](https://epsg.io/4326) coordinate, without wrapping its longitude.
+    pub(crate) fn pixel_to_unwrapped_coordinate(&self, pixel: Coord) -> Coord {
+        let coord = self
+            .projection
+            .unproject(self.pixel_to_tile(pixel), self.zoom);
+
+        self.axis_order.from_lon_lat(coord)
+    }
+
+    /// Converts a physical `pixel` into tile-space at the [`zoom`](Self::zoom) level.
+    pub(crate) fn pixel_to_tile(&self, pixel: Coord) -> Coord {
+        let pixel_ratio = self.pixel_ratio as f64;
+
+        let offset = geo::point!(
+            x: pixel.x / pixel_ratio - self.width as f64 / 2.0,
+            y: pixel.y / pixel_ratio - self.height as f64 / 2.0,
+        );
+
+        let tile_point = rotate(offset, self.bearing) / self.tile_size as f64;
+        self.project(self.center.0) + tile_point.0
+    }
+
+    /// Projects the `coord` into tile-space at the [`zoom`](Self::zoom) level.
+    fn project(&self, coord: Coord) -> Coord {
+        self.projection
+            .project(self.axis_order.to_lon_lat(coord), self.zoom)
+    }
+}
+
+/// An [ESRI world file](https://en.wikipedia.org/wiki/World_file), describing the affine transform from pixels of an image to a coordinate reference system.
+/// Formats to the six lines expected in `.pgw`, `.jgw`, and other world files.
+///
+/// See [`Snapshot::world_file`] for more details.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorldFile {
+    /// Size of a pixel along the `x` axis of the image, in the `x` axis of the coordinate reference system.
+    pub x_scale: f64,
+
+    /// Size of a pixel along the `x` axis of the image, in the `y` axis of the coordinate reference system.
+    pub y_skew: f64,
+
+    /// Size of a pixel along the `y` axis of the image, in the `x` axis of the coordinate reference system.
+    pub x_skew: f64,
+
+    /// Size of a pixel along the `y` axis of the image, in the `y` axis of the coordinate reference system.
+    /// Typically negative, as rows of the image grow downwards.
+    pub y_scale: f64,
+
+    /// Position of the center of the top-left pixel, in the `x` axis of the coordinate reference system.
+    pub x: f64,
+
+    /// Position of the center of the top-left pixel, in the `y` axis of the coordinate reference system.
+    pub y: f64,
+}
+
+impl fmt::Display for WorldFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.x_scale)?;
+        writeln!(f, "{}", self.y_skew)?;
+        writeln!(f, "{}", self.x_skew)?;
+        writeln!(f, "{}", self.y_scale)?;
+        writeln!(f, "{}", self.x)?;
+        writeln!(f, "{}", self.y)
+    }
+}
@@ -1,23 +1,70 @@
 //! Contains a [`SnaprBuilder`] implementation that constructs an [`AsyncTileFetcher`] with a [`tokio`] executor.
 
-use std::{fmt, thread};
+use std::{fmt, sync::Arc, thread};
 
 use tokio::runtime::Handle;
 
 use crate::{
     builder::macros::impl_snapr_builder,
     fetchers::{AsyncTileFetcher, BatchTileFetcher},
-    Error, Snapr, TileFetcher, Zoom,
+    projection::{Projection, WebMercator},
+    AxisOrder, Error, MissingTilePolicy, Padding, Snapr, TileFetcher, TileLayer, TileSizePolicy,
+    Zoom,
 };
 
 /// Builder structure for [`Snapr`].
+///
+/// Tiles are fetched on the [`tokio`] runtime the [`Snapr`] was built on.
+/// Each tile an [`AsyncIndividualTileFetcher`](crate::fetchers::AsyncIndividualTileFetcher) fails to fetch is handled on its own, according to the [`MissingTilePolicy`].
+///
+/// ## Example
+///
+/// ```rust
+/// use image::DynamicImage;
+/// use snapr::{tokio::SnaprBuilder, AsyncTileFetcher, MissingTilePolicy, Viewport};
+///
+/// async fn tile_fetcher(x: i32, y: i32, zoom: u8) -> Result<DynamicImage, snapr::Error> {
+///     match x % 2 {
+///         0 => Ok(DynamicImage::new_rgba8(256, 256)),
+///         _ => Err(snapr::Error::TileNotFound { x, y, zoom }),
+///     }
+/// }
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let handle = runtime.handle().clone();
+///
+/// // Tasks spawned on a current-thread runtime only run while it's blocked on, so it's driven from another thread.
+/// std::thread::spawn(move || runtime.block_on(std::future::pending::<()>()));
+///
+/// let snapr = handle
+///     .block_on(
+///         SnaprBuilder::new()
+///             .with_tile_fetcher(AsyncTileFetcher::individual(tile_fetcher))
+///             .with_missing_tile_policy(MissingTilePolicy::Transparent)
+///             .build(),
+///     )
+///     .unwrap();
+///
+/// let viewport = Viewport::new(geo::point!(x: 40.807997, y: -96.699724), 15.0);
+/// let snapshot = snapr.snapshot_with_viewport(Vec::new(), viewport).unwrap();
+///
+/// assert!(!snapshot.missing_tiles().is_empty());
+/// assert!(snapshot.missing_tiles().iter().all(|missing| missing.coord.x % 2 == 1));
+/// ```
 #[derive(Default)]
 pub struct SnaprBuilder<'a> {
     tile_fetcher: Option<AsyncTileFetcher<'a>>,
+    tile_layers: Vec<TileLayer<AsyncTileFetcher<'a>>>,
     tile_size: Option<u32>,
     height: Option<u32>,
     width: Option<u32>,
     zoom: Option<Zoom>,
+    padding: Option<Padding>,
+    pixel_ratio: Option<f32>,
+    projection: Option<Arc<dyn Projection>>,
+    axis_order: Option<AxisOrder>,
+    missing_tile_policy: Option<MissingTilePolicy>,
+    tile_size_policy: Option<TileSizePolicy>,
 }
 
 impl<'a> SnaprBuilder<'a> {
@@ -33,15 +80,15 @@ impl<'a> SnaprBuilder<'a> {
     ///     todo!()
     /// }
     ///
-    /// #[tokio::main]
-    /// async fn main() {
-    ///     let snapr = SnaprBuilder::new()
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    ///
+    /// let snapr = runtime.block_on(
+    ///     SnaprBuilder::new()
     ///         .with_tile_fetcher(AsyncTileFetcher::individual(tile_fetcher))
-    ///         .build()
-    ///         .await;
+    ///         .build(),
+    /// );
     ///
-    ///     assert!(snapr.is_ok());
-    /// }
+    /// assert!(snapr.is_ok());
     /// ```
     #[cfg_attr(
         feature = "tracing",
@@ -59,11 +106,49 @@ impl<'a> SnaprBuilder<'a> {
         let height = self.height.unwrap_or(600);
         let width = self.width.unwrap_or(800);
         let zoom = self.zoom.unwrap_or_default();
+        let padding = self.padding.unwrap_or_default();
+        let pixel_ratio = self.pixel_ratio.unwrap_or(1.0);
+        let projection = self.projection.unwrap_or_else(|| Arc::new(WebMercator));
+        let axis_order = self.axis_order.unwrap_or_default();
+        let missing_tile_policy = self.missing_tile_policy.unwrap_or_default();
+        let tile_size_policy = self.tile_size_policy.unwrap_or_default();
+
+        if !zoom.is_valid() {
+            return Err(Error::Builder {
+                reason: "field `zoom` needs to be between zero and `Zoom::MAX_LEVEL`".to_string(),
+            });
+        }
+
+        if !(pixel_ratio.is_finite() && pixel_ratio > 0.0) {
+            return Err(Error::Builder {
+                reason: "field `pixel_ratio` needs to be a finite number greater than zero"
+                    .to_string(),
+            });
+        }
+
+        let handle = Handle::current();
+
+        let tile_layers = self
+            .tile_layers
+            .into_iter()
+            .map(|tile_layer| {
+                let fail_on_error = tile_layer.missing_tile_policy == MissingTilePolicy::Fail;
+
+                tile_layer.map_tile_fetcher(|inner| {
+                    TileFetcher::batch(TokioTileFetcher {
+                        handle: handle.clone(),
+                        inner,
+                        fail_on_error,
+                    })
+                })
+            })
+            .collect();
 
         let tile_fetcher = {
             let tokio_tile_fetcher = TokioTileFetcher {
-                handle: Handle::current(),
+                handle,
                 inner: tile_fetcher,
+                fail_on_error: missing_tile_policy == MissingTilePolicy::Fail,
             };
 
             #[cfg(feature = "tracing")]
@@ -79,10 +164,17 @@ impl<'a> SnaprBuilder<'a> {
 
         let snapr = crate::Snapr {
             tile_fetcher,
+            tile_layers,
             tile_size,
             height,
             width,
             zoom,
+            padding,
+            pixel_ratio,
+            projection,
+            axis_order,
+            missing_tile_policy,
+            tile_size_policy,
         };
 
         Ok(snapr)
@@ -98,10 +190,16 @@ impl_snapr_builder!(
 impl<'a> fmt::Debug for SnaprBuilder<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SnaprBuilder")
+            .field("tile_layers", &self.tile_layers)
             .field("tile_size", &self.tile_size)
             .field("height", &self.height)
             .field("width", &self.width)
             .field("zoom", &self.zoom)
+            .field("padding", &self.padding)
+            .field("pixel_ratio", &self.pixel_ratio)
+            .field("axis_order", &self.axis_order)
+            .field("missing_tile_policy", &self.missing_tile_policy)
+            .field("tile_size_policy", &self.tile_size_policy)
             .finish()
     }
 }
@@ -109,6 +207,10 @@ impl<'a> fmt::Debug for SnaprBuilder<'a> {
 struct TokioTileFetcher<'a> {
     handle: Handle,
     inner: AsyncTileFetcher<'a>,
+
+    /// Whether a single tile failing to be fetched fails the whole batch, as required by [`MissingTilePolicy::Fail`].
+    /// Otherwise, tiles that failed to be fetched are left out of the batch, to be substituted like any other missing tile.
+    fail_on_error: bool,
 }
 
 impl<'a> BatchTileFetcher for TokioTileFetcher<'a> {
@@ -140,7 +242,23 @@ impl<'a> BatchTileFetcher for TokioTileFetcher<'a> {
                 })
             });
 
-            spawned.join().map_err(|_| Error::AsynchronousTaskPanic)?
+            let tiles = spawned.join().map_err(|_| Error::AsynchronousTaskPanic)??;
+
+            tiles
+                .into_iter()
+                .filter_map(|(x, y, tile)| match tile {
+                    Ok(tile) => Some(Ok((x, y, tile))),
+                    Err(error) if self.fail_on_error => Some(Err(error)),
+                    Err(_error) => {
+                        #[cfg(feature = "tracing")]
+                        {
+                            tracing::warn!(x, y, error = %_error, "leaving tile that failed to be fetched out of the batch");
+                        }
+
+                        None
+                    }
+                })
+                .collect()
         })
     }
 }